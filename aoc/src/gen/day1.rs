@@ -0,0 +1,15 @@
+//! Calorie counts. `size` is the number of elves.
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let items = rng.gen_range(1..=15);
+            (0..items)
+                .map(|_| format!("{}\n", rng.gen_range(1000..=70000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
@@ -0,0 +1,25 @@
+//! CPU instructions. `size` is the number of CPU cycles, at least enough to draw the screen.
+
+use rand::Rng;
+
+const SCREEN_CYCLES: usize = 240;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result = String::new();
+    let mut x_register = 1i64;
+    let mut cycle = 0;
+    while cycle < size.max(SCREEN_CYCLES) {
+        if rng.gen_bool(0.3) {
+            result += "noop\n";
+            cycle += 1;
+        } else {
+            // Keep the sprite on or near the screen, like in the real inputs.
+            let target = rng.gen_range(-1..=40);
+            let addend = (target - x_register).clamp(-20, 20);
+            x_register += addend;
+            result += &format!("addx {addend}\n");
+            cycle += 2;
+        }
+    }
+    result
+}
@@ -0,0 +1,112 @@
+//! Monkeys throwing items around. `size` is the number of monkeys.
+
+use rand::Rng;
+
+const DIVISORS: &[i64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23];
+const ROUNDS: usize = 20;
+
+enum Operation {
+    Add(i64),
+    Mul(i64),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<i64>,
+    operation: Operation,
+    divisor: i64,
+    targets: (usize, usize),
+}
+
+impl Monkey {
+    fn generate(rng: &mut impl Rng, id: usize, count: usize, square: bool) -> Self {
+        let items = (0..rng.gen_range(1..=8))
+            .map(|_| rng.gen_range(50..100))
+            .collect();
+        let operation = if square {
+            Operation::Square
+        } else if rng.gen_bool(0.5) {
+            Operation::Add(rng.gen_range(1..=8))
+        } else {
+            Operation::Mul(rng.gen_range(2..=19))
+        };
+        let mut other = || loop {
+            let target = rng.gen_range(0..count);
+            if target != id {
+                break target;
+            }
+        };
+        let targets = (other(), other());
+        Self {
+            items,
+            operation,
+            divisor: DIVISORS[id % DIVISORS.len()],
+            targets,
+        }
+    }
+
+    fn write(&self, id: usize, result: &mut String) {
+        let items: Vec<String> = self.items.iter().map(i64::to_string).collect();
+        let operation = match self.operation {
+            Operation::Add(n) => format!("old + {n}"),
+            Operation::Mul(n) => format!("old * {n}"),
+            Operation::Square => "old * old".to_string(),
+        };
+        *result += &format!("Monkey {id}:\n");
+        *result += &format!("  Starting items: {}\n", items.join(", "));
+        *result += &format!("  Operation: new = {operation}\n");
+        *result += &format!("  Test: divisible by {}\n", self.divisor);
+        *result += &format!("    If true: throw to monkey {}\n", self.targets.0);
+        *result += &format!("    If false: throw to monkey {}\n", self.targets.1);
+    }
+}
+
+/// Play the rounds of part 1, which keep the actual worry levels rather than reducing
+/// them modulo the divisors, and check that they never overflow.
+fn worry_levels_fit(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<i64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _ in 0..ROUNDS {
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            for worry in std::mem::take(&mut items[idx]) {
+                let new_worry = match monkey.operation {
+                    Operation::Add(n) => worry.checked_add(n),
+                    Operation::Mul(n) => worry.checked_mul(n),
+                    Operation::Square => worry.checked_mul(worry),
+                };
+                let Some(new_worry) = new_worry else {
+                    return false;
+                };
+                let new_worry = new_worry / 3;
+                let target = if new_worry % monkey.divisor == 0 {
+                    monkey.targets.0
+                } else {
+                    monkey.targets.1
+                };
+                items[target].push(new_worry);
+            }
+        }
+    }
+    true
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let count = size.max(2);
+    let monkeys = loop {
+        let square = rng.gen_range(0..count);
+        let monkeys: Vec<Monkey> = (0..count)
+            .map(|id| Monkey::generate(rng, id, count, id == square))
+            .collect();
+        if worry_levels_fit(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    let mut result = String::new();
+    for (id, monkey) in monkeys.iter().enumerate() {
+        if id > 0 {
+            result.push('\n');
+        }
+        monkey.write(id, &mut result);
+    }
+    result
+}
@@ -0,0 +1,59 @@
+//! A heightmap with a start and an end. `size` is the width of the map,
+//! which is a quarter as tall as it is wide.
+
+use std::collections::BTreeSet;
+
+use rand::Rng;
+
+const HIGHEST: u32 = 25;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let width = size.max(32);
+    let height = (width / 4).max(3);
+    let start = (height / 2, 0);
+    let distance = |(row, col): (usize, usize)| row.abs_diff(start.0) + col.abs_diff(start.1);
+
+    // Heights rise steadily with the distance from the start, so that the top is
+    // just about reachable at the far side of the map.
+    let max_distance = distance((0, width - 1));
+    let step = (max_distance / (HIGHEST as usize + 1)).max(1);
+    let base_height = |pt| (distance(pt) / step).min(HIGHEST as usize) as u32;
+
+    let end = loop {
+        let pt = (rng.gen_range(0..height), rng.gen_range(0..width));
+        if base_height(pt) == HIGHEST {
+            break pt;
+        }
+    };
+
+    // Walk right and then up or down to the end, so that every step of this path
+    // moves away from the start and climbs by at most one.
+    let mut path = BTreeSet::new();
+    path.extend((0..=end.1).map(|col| (start.0, col)));
+    if end.0 < start.0 {
+        path.extend((end.0..start.0).map(|row| (row, end.1)));
+    } else {
+        path.extend((start.0 + 1..=end.0).map(|row| (row, end.1)));
+    }
+
+    let mut result = String::new();
+    for row in 0..height {
+        for col in 0..width {
+            let pt = (row, col);
+            let c = if pt == start {
+                'S'
+            } else if pt == end {
+                'E'
+            } else {
+                let mut elevation = base_height(pt);
+                if !path.contains(&pt) && rng.gen_bool(0.2) {
+                    elevation = rng.gen_range(0..=elevation);
+                }
+                char::from_digit(elevation + 10, 36).unwrap()
+            };
+            result.push(c);
+        }
+        result.push('\n');
+    }
+    result
+}
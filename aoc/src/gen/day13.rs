@@ -0,0 +1,32 @@
+//! Pairs of distress signal packets. `size` is the number of pairs.
+
+use rand::Rng;
+
+fn packet(rng: &mut impl Rng, depth: usize, result: &mut String) {
+    result.push('[');
+    for idx in 0..rng.gen_range(0..=5) {
+        if idx > 0 {
+            result.push(',');
+        }
+        if depth < 4 && rng.gen_bool(0.3) {
+            packet(rng, depth + 1, result);
+        } else {
+            *result += &rng.gen_range(0..=10).to_string();
+        }
+    }
+    result.push(']');
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result = String::new();
+    for idx in 0..size.max(1) {
+        if idx > 0 {
+            result.push('\n');
+        }
+        packet(rng, 0, &mut result);
+        result.push('\n');
+        packet(rng, 0, &mut result);
+        result.push('\n');
+    }
+    result
+}
@@ -0,0 +1,48 @@
+//! Rock paths in a cave. `size` is the number of paths.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+/// No rock is ever placed in this column, so sand eventually falls past the rocks
+/// through it instead of piling up all the way to the source.
+const OPEN_COLUMN: i64 = 503;
+
+const LEFT_SIDE: RangeInclusive<i64> = 440..=OPEN_COLUMN - 1;
+const RIGHT_SIDE: RangeInclusive<i64> = OPEN_COLUMN + 1..=560;
+const DEPTHS: RangeInclusive<i64> = 10..=170;
+
+fn path(rng: &mut impl Rng) -> String {
+    let side = if rng.gen_bool(0.5) {
+        LEFT_SIDE
+    } else {
+        RIGHT_SIDE
+    };
+    let mut point = (rng.gen_range(side.clone()), rng.gen_range(DEPTHS));
+    let mut points = vec![point];
+    let mut horizontal = rng.gen_bool(0.5);
+    let length = rng.gen_range(2..=6);
+    while points.len() < length {
+        let delta = rng.gen_range(-10..=10);
+        let next = if horizontal {
+            ((point.0 + delta).clamp(*side.start(), *side.end()), point.1)
+        } else {
+            (
+                point.0,
+                (point.1 + delta).clamp(*DEPTHS.start(), *DEPTHS.end()),
+            )
+        };
+        if next != point {
+            point = next;
+            points.push(point);
+            horizontal = !horizontal;
+        }
+    }
+
+    let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+    points.join(" -> ")
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| path(rng) + "\n").collect()
+}
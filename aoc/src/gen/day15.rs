@@ -0,0 +1,50 @@
+//! Sensors and their closest beacons. `size` is the number of sensors.
+
+use rand::{seq::SliceRandom, Rng};
+
+const CUTOFF: i64 = 4000000;
+
+fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// A beacon at exactly the given distance from the sensor.
+fn beacon_at(rng: &mut impl Rng, sensor: (i64, i64), distance: i64) -> (i64, i64) {
+    let dx = rng.gen_range(-distance..=distance);
+    let dy = distance - dx.abs();
+    let dy = if rng.gen_bool(0.5) { dy } else { -dy };
+    (sensor.0 + dx, sensor.1 + dy)
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let hidden = (rng.gen_range(0..=CUTOFF), rng.gen_range(0..=CUTOFF));
+
+    // Four sensors diagonally around the distress beacon, each reaching just short of it,
+    // together cover the whole search area except for the distress beacon itself.
+    let reach = CUTOFF + 1;
+    let mut sensors = vec![];
+    for (sx, sy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let sensor = (hidden.0 + sx * reach, hidden.1 + sy * reach);
+        sensors.push((sensor, beacon_at(rng, sensor, 2 * reach - 1)));
+    }
+
+    // The rest are scattered around, and also stop short of the distress beacon.
+    let count = size.max(sensors.len());
+    while sensors.len() < count {
+        let sensor = (rng.gen_range(0..=CUTOFF), rng.gen_range(0..=CUTOFF));
+        let distance = manhattan(sensor, hidden);
+        if distance < 2 {
+            continue;
+        }
+        let radius = rng.gen_range(1..distance);
+        sensors.push((sensor, beacon_at(rng, sensor, radius)));
+    }
+
+    sensors.shuffle(rng);
+
+    let mut result = String::new();
+    for ((sx, sy), (bx, by)) in sensors {
+        result += &format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}\n");
+    }
+    result
+}
@@ -0,0 +1,66 @@
+//! A network of valves and tunnels. `size` is the number of valves, a quarter of which
+//! (up to the 19 that the solution supports) have a non-zero flow rate.
+
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+const MAX_NON_ZERO_VALVES: usize = 19;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let count = size.clamp(2, 26 * 26);
+
+    let mut names = ('A'..='Z')
+        .cartesian_product('A'..='Z')
+        .map(|(a, b)| format!("{a}{b}"))
+        .filter(|name| name != "AA")
+        .collect_vec();
+    names.shuffle(rng);
+    names.truncate(count - 1);
+    names.insert(0, "AA".to_string());
+
+    // A random spanning tree keeps every valve reachable, and a few more tunnels add loops.
+    let mut tunnels = vec![BTreeSet::new(); count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for idx in 1..count {
+        connect(idx, rng.gen_range(0..idx));
+    }
+    for _ in 0..count / 4 {
+        connect(rng.gen_range(0..count), rng.gen_range(0..count));
+    }
+
+    let non_zero = (count / 4).clamp(1, MAX_NON_ZERO_VALVES);
+    let mut flows = vec![0; count];
+    for flow in flows.iter_mut().skip(1).take(non_zero) {
+        *flow = rng.gen_range(3..=25);
+    }
+    flows[1..].shuffle(rng);
+
+    let mut lines = (0..count)
+        .map(|idx| {
+            let neighbors = tunnels[idx]
+                .iter()
+                .map(|n| names[*n].as_str())
+                .collect_vec();
+            let tunnels = if neighbors.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={}; {tunnels} {}\n",
+                names[idx],
+                flows[idx],
+                neighbors.join(", ")
+            )
+        })
+        .collect_vec();
+    lines.shuffle(rng);
+    lines.concat()
+}
@@ -0,0 +1,11 @@
+//! The jet pattern pushing the falling rocks. `size` is the length of the pattern.
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result: String = (0..size.max(1))
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect();
+    result.push('\n');
+    result
+}
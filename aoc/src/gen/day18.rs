@@ -0,0 +1,25 @@
+//! Lava droplet cubes. `size` is the number of cubes.
+
+use std::collections::BTreeSet;
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    // Keep the droplet dense enough to contain air pockets, like the real one.
+    let count = size.max(1);
+    let extent = ((count as f64 * 2.0).cbrt().ceil() as i64).max(2);
+
+    let mut cubes = BTreeSet::new();
+    let mut result = String::new();
+    while cubes.len() < count {
+        let cube = (
+            rng.gen_range(0..extent),
+            rng.gen_range(0..extent),
+            rng.gen_range(0..extent),
+        );
+        if cubes.insert(cube) {
+            result += &format!("{},{},{}\n", cube.0, cube.1, cube.2);
+        }
+    }
+    result
+}
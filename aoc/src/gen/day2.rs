@@ -0,0 +1,13 @@
+//! Rock paper scissors strategy guide. `size` is the number of rounds.
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let opponent = rng.gen_range('A'..='C');
+            let response = rng.gen_range('X'..='Z');
+            format!("{opponent} {response}\n")
+        })
+        .collect()
+}
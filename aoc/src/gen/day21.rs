@@ -0,0 +1,142 @@
+//! Monkeys yelling numbers and math operations. `size` is roughly the number of monkeys.
+//!
+//! The operations are chosen so that every division is exact, both for the number `humn`
+//! yells in part 1 and for the one that balances `root` in part 2.
+
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+enum Node {
+    Literal(i64),
+    /// The `humn` monkey, with the number it yells in part 1.
+    Human(i64),
+    Operation(char, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn operation(op: char, left: Node, right: Node) -> Self {
+        Node::Operation(op, Box::new(left), Box::new(right))
+    }
+}
+
+/// An expression tree of about `budget` monkeys, evaluating to exactly `value`.
+fn expression(rng: &mut impl Rng, value: i64, budget: usize) -> Node {
+    if value > 0 && (budget < 3 || rng.gen_bool(0.1)) {
+        return Node::Literal(value);
+    }
+
+    let left_budget = rng.gen_range(0..budget.max(1));
+    let right_budget = budget.saturating_sub(left_budget + 1);
+    let divisors = (2..=9).filter(|d| value % d == 0).collect_vec();
+    let choice = rng.gen_range(0..4);
+
+    let (op, left, right) = if value <= 1 {
+        let right = rng.gen_range(1..=100);
+        ('-', value + right, right)
+    } else if choice == 0 && !divisors.is_empty() {
+        let right = *divisors.choose(rng).unwrap();
+        ('*', value / right, right)
+    } else if choice == 1 && value < 1_000_000_000 {
+        let right = rng.gen_range(2..=5);
+        ('/', value * right, right)
+    } else if choice == 2 {
+        let right = rng.gen_range(1..=value.min(1000));
+        ('-', value + right, right)
+    } else {
+        let left = rng.gen_range(1..value);
+        ('+', left, value - left)
+    };
+
+    Node::operation(
+        op,
+        expression(rng, left, left_budget),
+        expression(rng, right, right_budget),
+    )
+}
+
+/// The chain of operations between `root` and `humn`, along with its value when `humn`
+/// yells the number that should balance `root` in part 2.
+///
+/// There are no divisions on this chain, so that any number `humn` yells gives exact results.
+fn human_chain(rng: &mut impl Rng, length: usize, budget: usize) -> (Node, i64) {
+    let mut node = Node::Human(rng.gen_range(1..=5000));
+    let mut value = rng.gen_range(1..=5000);
+
+    for _ in 0..length {
+        let (op, operand) = match rng.gen_range(0..3) {
+            0 => ('*', rng.gen_range(2..=5)),
+            1 => ('+', rng.gen_range(1..=1000)),
+            _ => ('-', rng.gen_range(1..=1000)),
+        };
+        let constant = expression(rng, operand, budget);
+        let human_on_left = rng.gen_bool(0.5);
+        value = match (op, human_on_left) {
+            ('*', _) => value * operand,
+            ('+', _) => value + operand,
+            (_, true) => value - operand,
+            (_, false) => operand - value,
+        };
+
+        node = if human_on_left {
+            Node::operation(op, node, constant)
+        } else {
+            Node::operation(op, constant, node)
+        };
+    }
+
+    (node, value)
+}
+
+fn unique_name(rng: &mut impl Rng, taken: &mut BTreeSet<String>) -> String {
+    loop {
+        let name = super::name(rng, 4);
+        if taken.insert(name.clone()) {
+            break name;
+        }
+    }
+}
+
+fn write(
+    rng: &mut impl Rng,
+    node: &Node,
+    name: &str,
+    taken: &mut BTreeSet<String>,
+    lines: &mut Vec<String>,
+) {
+    let line = match node {
+        Node::Literal(value) => format!("{name}: {value}"),
+        Node::Human(value) => format!("humn: {value}"),
+        Node::Operation(op, left, right) => {
+            let mut child_name = |child: &Node| match child {
+                Node::Human(_) => "humn".to_string(),
+                _ => unique_name(rng, taken),
+            };
+            let left_name = child_name(left);
+            let right_name = child_name(right);
+            write(rng, left, &left_name, taken, lines);
+            write(rng, right, &right_name, taken, lines);
+            format!("{name}: {left_name} {op} {right_name}")
+        }
+    };
+    lines.push(line);
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(8);
+    let chain_length = (size / 8).clamp(1, 12);
+    let (chain, balanced_value) = human_chain(rng, chain_length, size / 2 / chain_length);
+    let other = expression(rng, balanced_value, size / 2);
+    let root = if rng.gen_bool(0.5) {
+        Node::operation('+', chain, other)
+    } else {
+        Node::operation('+', other, chain)
+    };
+
+    let mut taken = BTreeSet::from(["root".to_string(), "humn".to_string()]);
+    let mut lines = vec![];
+    write(rng, &root, "root", &mut taken, &mut lines);
+    lines.shuffle(rng);
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
@@ -0,0 +1,119 @@
+//! A valley full of blizzards. `size` is the width of the valley, which is about a fifth
+//! as tall as it is wide.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use rand::Rng;
+
+struct Valley {
+    /// The interior of the valley, without the surrounding walls.
+    tiles: Vec<Vec<char>>,
+    width: i64,
+    height: i64,
+}
+
+impl Valley {
+    fn generate(rng: &mut impl Rng, width: usize, height: usize) -> Self {
+        let tiles = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|col| {
+                        // Blizzards moving up or down the entrance and exit columns
+                        // would escape the valley.
+                        let vertical_allowed = col != 0 && col != width - 1;
+                        match rng.gen_range(0..10) {
+                            4 | 5 => '>',
+                            6 | 7 => '<',
+                            8 if vertical_allowed => '^',
+                            9 if vertical_allowed => 'v',
+                            _ => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            tiles,
+            width: width as i64,
+            height: height as i64,
+        }
+    }
+
+    /// Whether the tile is free of walls and blizzards at the given time.
+    /// The entrance is at row -1, and the exit at row `height`.
+    fn open_at(&self, (row, col): (i64, i64), time: i64) -> bool {
+        if (row, col) == (-1, 0) || (row, col) == (self.height, self.width - 1) {
+            return true;
+        }
+        if !(0..self.height).contains(&row) || !(0..self.width).contains(&col) {
+            return false;
+        }
+
+        let tile = |r: i64, c: i64| {
+            self.tiles[r.rem_euclid(self.height) as usize][c.rem_euclid(self.width) as usize]
+        };
+        tile(row, col - time) != '>'
+            && tile(row, col + time) != '<'
+            && tile(row - time, col) != 'v'
+            && tile(row + time, col) != '^'
+    }
+
+    /// The earliest time at which `to` can be reached, setting off from `from` at `start`.
+    fn crossing(&self, from: (i64, i64), to: (i64, i64), start: i64) -> Option<i64> {
+        // The blizzards repeat after this many steps, so there is no point waiting longer.
+        let period = self.width * self.height;
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::from([(start, from)]);
+        while let Some((time, (row, col))) = queue.pop_front() {
+            for next in [
+                (row, col),
+                (row - 1, col),
+                (row + 1, col),
+                (row, col - 1),
+                (row, col + 1),
+            ] {
+                if !self.open_at(next, time + 1) {
+                    continue;
+                }
+                if next == to {
+                    return Some(time + 1);
+                }
+                if visited.insert(((time + 1) % period, next)) {
+                    queue.push_back((time + 1, next));
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether both parts have a solution: there, back, and there again.
+    fn solvable(&self) -> bool {
+        let entrance = (-1, 0);
+        let exit = (self.height, self.width - 1);
+        self.crossing(entrance, exit, 0)
+            .and_then(|time| self.crossing(exit, entrance, time))
+            .and_then(|time| self.crossing(entrance, exit, time))
+            .is_some()
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let width = size.max(3);
+    let height = (width / 5).max(1);
+    let valley = loop {
+        let valley = Valley::generate(rng, width, height);
+        if valley.solvable() {
+            break valley;
+        }
+    };
+
+    let mut result = String::new();
+    result += &format!("#.{}\n", "#".repeat(width));
+    for row in &valley.tiles {
+        result.push('#');
+        result.extend(row.iter());
+        result += "#\n";
+    }
+    result += &format!("{}.#\n", "#".repeat(width));
+    result
+}
@@ -0,0 +1,18 @@
+//! Fuel requirements in SNAFU. `size` is the number of requirements.
+
+use rand::Rng;
+
+const DIGITS: [char; 5] = ['=', '-', '0', '1', '2'];
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let length = rng.gen_range(1..=20);
+            let mut number = String::with_capacity(length + 1);
+            number.push(['1', '2'][rng.gen_range(0..2)]);
+            number.extend((1..length).map(|_| DIGITS[rng.gen_range(0..DIGITS.len())]));
+            number.push('\n');
+            number
+        })
+        .collect()
+}
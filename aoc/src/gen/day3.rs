@@ -0,0 +1,45 @@
+//! Rucksack contents. `size` is the number of groups of three elves.
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let mut items = ITEMS.chars().collect_vec();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+
+        // Split the other items into three pools, and keep each elf out of one of them,
+        // so that the badge is the only item all three elves carry.
+        let pools = items.chunks(items.len() / 3 + 1).collect_vec();
+        for excluded in 0..3 {
+            let mut allowed = pools
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != excluded)
+                .flat_map(|(_, pool)| pool.iter().copied())
+                .collect_vec();
+            allowed.shuffle(rng);
+
+            // Draw each compartment from a different half, apart from the shared item.
+            let shared = allowed.pop().unwrap();
+            let (first_pool, second_pool) = allowed.split_at(allowed.len() / 2);
+
+            let half = rng.gen_range(3..=16);
+            let mut first = vec![shared];
+            let mut second = vec![shared];
+            first.extend((1..half).map(|_| *first_pool.choose(rng).unwrap()));
+            second.extend((2..half).map(|_| *second_pool.choose(rng).unwrap()));
+            second.push(badge);
+            first.shuffle(rng);
+            second.shuffle(rng);
+
+            result.extend(first.into_iter().chain(second));
+            result.push('\n');
+        }
+    }
+    result
+}
@@ -0,0 +1,18 @@
+//! Section assignment pairs. `size` is the number of pairs.
+
+use rand::Rng;
+
+fn range(rng: &mut impl Rng) -> (u32, u32) {
+    let start = rng.gen_range(1..100);
+    (start, rng.gen_range(start..100))
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let (a, b) = range(rng);
+            let (c, d) = range(rng);
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
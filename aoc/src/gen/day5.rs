@@ -0,0 +1,54 @@
+//! Crate stacks and rearrangement steps. `size` is the number of steps.
+
+use rand::Rng;
+
+const STACK_COUNT: usize = 9;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..STACK_COUNT)
+        .map(|_| {
+            let height = rng.gen_range(1..=8);
+            (0..height).map(|_| rng.gen_range('A'..='Z')).collect()
+        })
+        .collect();
+
+    let tallest = stacks.iter().map(Vec::len).max().unwrap();
+    let mut result = String::new();
+    for level in (0..tallest).rev() {
+        let line: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_string(),
+            })
+            .collect();
+        result += &line.join(" ");
+        result.push('\n');
+    }
+    let ids: Vec<String> = (1..=STACK_COUNT).map(|id| format!(" {id} ")).collect();
+    result += &ids.join(" ");
+    result += "\n\n";
+
+    // Always leave at least one crate behind, so every stack has a top crate at the end.
+    for _ in 0..size.max(1) {
+        let from = loop {
+            let from = rng.gen_range(0..STACK_COUNT);
+            if stacks[from].len() > 1 {
+                break from;
+            }
+        };
+        let to = loop {
+            let to = rng.gen_range(0..STACK_COUNT);
+            if to != from {
+                break to;
+            }
+        };
+        let count = rng.gen_range(1..stacks[from].len());
+
+        let remaining = stacks[from].len() - count;
+        let moved = stacks[from].split_off(remaining);
+        stacks[to].extend(moved);
+        result += &format!("move {count} from {} to {}\n", from + 1, to + 1);
+    }
+    result
+}
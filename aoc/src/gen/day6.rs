@@ -0,0 +1,39 @@
+//! A datastream buffer. `size` is roughly the number of characters before the
+//! start-of-message marker.
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+const PACKET_MARKER_LENGTH: usize = 4;
+const MESSAGE_MARKER_LENGTH: usize = 14;
+
+/// Random characters from an alphabet too small to contain a marker of the given length.
+fn filler(rng: &mut impl Rng, marker_length: usize, count: usize) -> String {
+    let mut alphabet = ('a'..='z').collect_vec();
+    alphabet.shuffle(rng);
+    alphabet.truncate(marker_length - 1);
+    (0..count).map(|_| *alphabet.choose(rng).unwrap()).collect()
+}
+
+fn marker(rng: &mut impl Rng, marker_length: usize) -> String {
+    let mut alphabet = ('a'..='z').collect_vec();
+    alphabet.shuffle(rng);
+    alphabet.into_iter().take(marker_length).collect()
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(2 * MESSAGE_MARKER_LENGTH);
+    let packet_start = rng.gen_range(0..size / 2);
+
+    let mut result = filler(rng, PACKET_MARKER_LENGTH, packet_start);
+    result += &marker(rng, PACKET_MARKER_LENGTH);
+    result += &filler(
+        rng,
+        MESSAGE_MARKER_LENGTH,
+        size - packet_start - PACKET_MARKER_LENGTH,
+    );
+    result += &marker(rng, MESSAGE_MARKER_LENGTH);
+    result += &filler(rng, 26, size);
+    result.push('\n');
+    result
+}
@@ -0,0 +1,84 @@
+//! A terminal session exploring a filesystem. `size` is the number of directories.
+
+use std::collections::BTreeSet;
+
+use rand::Rng;
+
+const FULL_SPACE: u64 = 70000000;
+const REQUIRED_FREE_SPACE: u64 = 30000000;
+
+const EXTENSIONS: &[&str] = &["", ".txt", ".dat", ".log", ".lst", ".ext"];
+
+#[derive(Default)]
+struct Directory {
+    name: String,
+    children: Vec<usize>,
+    files: Vec<(String, u64)>,
+}
+
+/// A name not yet taken by another entry of the same directory.
+fn unique_name(rng: &mut impl Rng, taken: &mut BTreeSet<String>, extension: &str) -> String {
+    loop {
+        let length = rng.gen_range(1..=8);
+        let name = super::name(rng, length) + extension;
+        if taken.insert(name.clone()) {
+            break name;
+        }
+    }
+}
+
+fn write_session(directories: &[Directory], idx: usize, result: &mut String) {
+    let directory = &directories[idx];
+    *result += "$ ls\n";
+    for child in &directory.children {
+        *result += &format!("dir {}\n", directories[*child].name);
+    }
+    for (name, size) in &directory.files {
+        *result += &format!("{size} {name}\n");
+    }
+    for child in &directory.children {
+        *result += &format!("$ cd {}\n", directories[*child].name);
+        write_session(directories, *child, result);
+        *result += "$ cd ..\n";
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut directories = vec![Directory::default()];
+    let mut names = vec![BTreeSet::new()];
+    for idx in 1..size.max(1) {
+        let parent = rng.gen_range(0..idx);
+        let name = unique_name(rng, &mut names[parent], "");
+        directories[parent].children.push(idx);
+        directories.push(Directory {
+            name,
+            ..Default::default()
+        });
+        names.push(BTreeSet::new());
+    }
+
+    // Files get random weights, which are scaled so that the filesystem is too full
+    // for the update, but not so full that it doesn't fit on the disk at all.
+    let mut weights = vec![];
+    for (idx, directory) in directories.iter_mut().enumerate() {
+        for _ in 0..rng.gen_range(usize::from(idx == 0)..=4) {
+            let extension = EXTENSIONS[rng.gen_range(0..EXTENSIONS.len())];
+            let name = unique_name(rng, &mut names[idx], extension);
+            directory.files.push((name, 0));
+            weights.push(rng.gen_range(1..=100u64));
+        }
+    }
+    let total_size =
+        rng.gen_range(FULL_SPACE - REQUIRED_FREE_SPACE + 1000000..FULL_SPACE - 1000000);
+    let total_weight: u64 = weights.iter().sum();
+    let mut weights = weights.into_iter();
+    for directory in &mut directories {
+        for (_, size) in &mut directory.files {
+            *size = (weights.next().unwrap() * total_size / total_weight).max(1);
+        }
+    }
+
+    let mut result = "$ cd /\n".to_string();
+    write_session(&directories, 0, &mut result);
+    result
+}
@@ -0,0 +1,14 @@
+//! A grid of tree heights. `size` is the side length of the grid.
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size).map(|_| rng.gen_range('0'..='9')).collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
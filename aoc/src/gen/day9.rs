@@ -0,0 +1,12 @@
+//! Rope head motions. `size` is the number of motions.
+
+use rand::Rng;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let direction = ['U', 'D', 'L', 'R'][rng.gen_range(0..4)];
+            format!("{direction} {}\n", rng.gen_range(1..=20))
+        })
+        .collect()
+}
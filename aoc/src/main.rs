@@ -0,0 +1,161 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+mod gen;
+mod minimize;
+mod repl;
+mod run_all;
+mod runner;
+mod serve;
+#[cfg(target_os = "linux")]
+mod watch;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let limits = runner::Limits::from_args(&mut args);
+    let run_options = run_all::Options::from_args(&mut args);
+    let serve_options = serve::Options::from_args(&mut args);
+    let minimize_options = minimize::Options::from_args(&mut args);
+    let mut reversed_args: Vec<_> = args.iter().map(|x| x.as_str()).rev().collect();
+
+    reversed_args
+        .pop()
+        .expect("Expected the executable name to be the first argument, but was missing");
+
+    let command = reversed_args.pop().expect("command");
+    match command {
+        "gen" => {
+            let day = reversed_args.pop().expect("day number, or `all`");
+            let size = reversed_args
+                .pop()
+                .expect("input size")
+                .parse::<usize>()
+                .unwrap();
+            let seed = reversed_args
+                .pop()
+                .expect("random seed")
+                .parse::<u64>()
+                .unwrap();
+            let output = reversed_args.pop();
+
+            if day == "all" {
+                let output_dir = Path::new(output.expect("output directory"));
+                fs::create_dir_all(output_dir).unwrap();
+                for day in gen::DAYS {
+                    let input = gen::generate(*day, size, seed).unwrap();
+                    fs::write(output_dir.join(format!("day{day}.txt")), input).unwrap();
+                }
+            } else {
+                let day = day.parse::<u32>().unwrap();
+                let Some(input) = gen::generate(day, size, seed) else {
+                    eprintln!("no input generator for day {day}");
+                    process::exit(1);
+                };
+                match output {
+                    Some(output_file) => fs::write(output_file, input).unwrap(),
+                    None => print!("{input}"),
+                }
+            }
+        }
+        "run-all" => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            if !run_all::run_all(root, &run_options, &limits) {
+                process::exit(1);
+            }
+        }
+        "serve" => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            let address = reversed_args.pop().unwrap_or("127.0.0.1:8022");
+            if let Err(e) = serve::serve(root, address, serve_options, limits) {
+                eprintln!("error: failed to serve on {address}: {e}");
+                process::exit(1);
+            }
+        }
+        "repl" => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            let day = reversed_args
+                .pop()
+                .expect("day number")
+                .parse::<u32>()
+                .unwrap();
+            let input = reversed_args.pop();
+            if !repl::DAYS.contains(&day) {
+                eprintln!("no REPL for day {day}, only for days {:?}", repl::DAYS);
+                process::exit(1);
+            }
+            match repl::repl(root, day, input) {
+                Ok(status) if status.success() => {}
+                Ok(status) => process::exit(status.code().unwrap_or(1)),
+                Err(e) => {
+                    eprintln!("error: failed to start the REPL for day {day}: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        "minimize" => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            let day = reversed_args
+                .pop()
+                .expect("day number")
+                .parse::<u32>()
+                .unwrap();
+            let part = reversed_args
+                .pop()
+                .expect("part number")
+                .parse::<u32>()
+                .unwrap();
+            let failure = reversed_args
+                .pop()
+                .expect("failure: panic, mismatch or timeout");
+            let Some(failure) = minimize::Failure::from_name(failure) else {
+                eprintln!("unknown failure {failure}, expected panic, mismatch or timeout");
+                process::exit(1);
+            };
+            let input = Path::new(reversed_args.pop().expect("input file"));
+            let output = match reversed_args.pop() {
+                Some(output) => PathBuf::from(output),
+                None => run_all::day_dir(root, day).join("minimal.txt"),
+            };
+            let result = minimize::minimize(
+                root,
+                day,
+                part,
+                failure,
+                input,
+                &output,
+                &minimize_options,
+                &limits,
+            );
+            if let Err(e) = result {
+                eprintln!("error: {e}");
+                process::exit(1);
+            }
+        }
+        #[cfg(target_os = "linux")]
+        "watch" => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            let day = reversed_args
+                .pop()
+                .expect("day number")
+                .parse::<u32>()
+                .unwrap();
+            let parts = match reversed_args.pop() {
+                Some(part) => vec![part.parse::<u32>().unwrap()],
+                None => vec![1, 2],
+            };
+            if let Err(e) = watch::watch(root, day, &parts, &limits) {
+                eprintln!("error: failed to watch day {day}: {e}");
+                process::exit(1);
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        "watch" => {
+            eprintln!("error: watching needs inotify, which is only available on Linux");
+            process::exit(1);
+        }
+        _ => unreachable!("{}", command),
+    }
+}
@@ -0,0 +1,256 @@
+//! Shrinking an input that makes a solution fail into a small one that fails the same way,
+//! by delta debugging: remove chunks of paragraphs, then of lines, for as long as the failure
+//! persists, halving the chunks whenever none of them can go.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use common::{answer::Answer, input};
+use serde_json::Value;
+
+use crate::{
+    run_all::{self, Options as RunOptions, Outcome, Status},
+    runner::Limits,
+};
+
+/// The way the input has to keep failing while it's minimised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The solution panics, with the same message as on the original input, or its process
+    /// dies without reporting anything, like on a stack overflow.
+    Panic,
+
+    /// The solution's answer differs from the oracle's.
+    Mismatch,
+
+    /// The solution runs past the `--timeout`.
+    Timeout,
+}
+
+impl Failure {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "panic" => Some(Failure::Panic),
+            "mismatch" => Some(Failure::Mismatch),
+            "timeout" => Some(Failure::Timeout),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The program and arguments that print the right answer for an input file, which is
+    /// passed as the last argument, for checking answers against a brute-force solution.
+    pub oracle: Option<Vec<String>>,
+}
+
+impl Options {
+    /// Take the `--oracle <command>` option out of the command line arguments, leaving the
+    /// positional arguments in place. The command is split on whitespace.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut options = Self::default();
+        if let Some(idx) = args.iter().position(|arg| arg == "--oracle") {
+            args.remove(idx);
+            let command = args.get(idx).cloned().expect("oracle command");
+            args.remove(idx);
+            options.oracle = Some(command.split_whitespace().map(String::from).collect());
+        }
+        options
+    }
+}
+
+/// Runs a part on candidate inputs, and tells whether each one fails the way the original
+/// input did.
+struct Checker<'a> {
+    root: &'a Path,
+    day: u32,
+    part: u32,
+    failure: Failure,
+    oracle: Option<&'a [String]>,
+    limits: &'a Limits,
+    candidate_file: PathBuf,
+
+    /// The original input's panic message, which the candidates have to panic with too, so
+    /// that the minimiser doesn't wander off to a different bug. It's `Value::Null` if the
+    /// process died without reporting one.
+    panic_message: Option<Value>,
+    runs: usize,
+}
+
+impl Checker<'_> {
+    fn run(&mut self, text: &str) -> Outcome {
+        fs::write(&self.candidate_file, text).expect("failed to write the candidate input");
+        self.runs += 1;
+        let file = self
+            .candidate_file
+            .to_str()
+            .expect("non-UTF-8 temporary directory");
+        run_all::run(self.root, self.day, self.part, &[file], None, self.limits)
+    }
+
+    fn fails(&mut self, text: &str) -> bool {
+        let outcome = self.run(text);
+        match self.failure {
+            Failure::Panic => panic_message(&outcome) == self.panic_message.as_ref(),
+            Failure::Timeout => outcome.status == Status::Timeout,
+            Failure::Mismatch => match (outcome.answer, self.oracle_answer()) {
+                (Some(answer), Some(expected)) => !expected.matches(&answer),
+                _ => false,
+            },
+        }
+    }
+
+    /// The oracle's answer for the candidate input, or `None` if it failed, in which case
+    /// there's no telling whether the solution is right.
+    fn oracle_answer(&self) -> Option<Answer> {
+        let (program, args) = self.oracle?.split_first()?;
+        let output = Command::new(program)
+            .args(args)
+            .arg(&self.candidate_file)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let answer = String::from_utf8(output.stdout).ok()?;
+        (output.status.success() && !answer.trim().is_empty()).then(|| Answer::parse(&answer))
+    }
+}
+
+/// The message a run panicked with, or `None` if it didn't panic.
+fn panic_message(outcome: &Outcome) -> Option<&Value> {
+    match &outcome.error {
+        Some(error) if error["kind"] == "panic" => Some(&error["message"]),
+        None if outcome.status == Status::Panicked => Some(&Value::Null),
+        _ => None,
+    }
+}
+
+/// Remove chunks of `units` for as long as what's left still fails, starting with halves and
+/// going down to single units, and return what's left. Every unit of the result is needed:
+/// removing any one of them makes the failure go away.
+fn ddmin<T: Clone>(mut units: Vec<T>, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunks = 2;
+    while units.len() >= 2 {
+        let chunk_len = units.len().div_ceil(chunks);
+        let reduced = (0..units.len()).step_by(chunk_len).find_map(|start| {
+            let end = (start + chunk_len).min(units.len());
+            let candidate: Vec<T> = [&units[..start], &units[end..]].concat();
+            fails(&candidate).then_some(candidate)
+        });
+        match reduced {
+            Some(candidate) => {
+                units = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= units.len() => break,
+            None => chunks = (chunks * 2).min(units.len()),
+        }
+    }
+    units
+}
+
+fn count_lines(text: &str) -> String {
+    match input::lines(text).count() {
+        1 => "1 line".to_string(),
+        lines => format!("{lines} lines"),
+    }
+}
+
+/// The text of the units that are left, joined back together.
+fn join(units: &[&str], separator: &str) -> String {
+    let mut text = units.join(separator);
+    if !text.is_empty() {
+        text += "\n";
+    }
+    text
+}
+
+/// Remove as many of the units as possible, writing each smaller failing input to `output`
+/// as it's found, so that an interrupted run keeps its progress.
+fn shrink(checker: &mut Checker<'_>, units: Vec<&str>, separator: &str, output: &Path) -> String {
+    let units = ddmin(units, |candidate| {
+        let candidate = join(candidate, separator);
+        let fails = checker.fails(&candidate);
+        if fails {
+            println!("{}", count_lines(&candidate));
+            let _ = fs::write(output, &candidate);
+        }
+        fails
+    });
+    join(&units, separator)
+}
+
+/// Shrink `input` to a smaller input on which the part still fails in the same way, and
+/// write it to `output`.
+#[allow(clippy::too_many_arguments)]
+pub fn minimize(
+    root: &Path,
+    day: u32,
+    part: u32,
+    failure: Failure,
+    input: &Path,
+    output: &Path,
+    options: &Options,
+    limits: &Limits,
+) -> Result<(), String> {
+    if failure == Failure::Mismatch && options.oracle.is_none() {
+        return Err("checking for mismatches needs an --oracle command".to_string());
+    }
+    let content = fs::read_to_string(input)
+        .map_err(|e| format!("failed to read {}: {e}", input.display()))?;
+    let text = input::normalize(&content);
+
+    println!("building day {day}");
+    if !run_all::build(root, day, &RunOptions::default()) {
+        return Err(format!("day {day} failed to build"));
+    }
+
+    let mut checker = Checker {
+        root,
+        day,
+        part,
+        failure,
+        oracle: options.oracle.as_deref(),
+        limits,
+        candidate_file: env::temp_dir().join(format!("aoc-minimize-{}.txt", process::id())),
+        panic_message: None,
+        runs: 0,
+    };
+    let fails = match failure {
+        Failure::Panic => {
+            let outcome = checker.run(&text);
+            checker.panic_message = panic_message(&outcome).cloned();
+            checker.panic_message.is_some()
+        }
+        _ => checker.fails(&text),
+    };
+    if !fails {
+        let _ = fs::remove_file(&checker.candidate_file);
+        return Err(format!(
+            "part {part} doesn't fail that way on {}",
+            input.display()
+        ));
+    }
+    if let Some(message) = &checker.panic_message {
+        println!("panics with {message}");
+    }
+
+    println!("{}", count_lines(&text));
+    let paragraphs = input::paragraphs(&text).collect();
+    let text = shrink(&mut checker, paragraphs, "\n\n", output);
+    let lines = input::lines(&text).collect();
+    let text = shrink(&mut checker, lines, "\n", output);
+
+    let _ = fs::remove_file(&checker.candidate_file);
+    fs::write(output, &text).map_err(|e| format!("failed to write {}: {e}", output.display()))?;
+    println!(
+        "wrote a {}-line input to {} after {} runs",
+        input::lines(&text).count(),
+        output.display(),
+        checker.runs
+    );
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! Starting a day's REPL, for exploring a puzzle input interactively.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+use crate::run_all::{self, Options};
+
+/// The days that have a REPL.
+pub const DAYS: &[u32] = &[5, 7, 13, 14, 15, 17, 21, 24];
+
+/// Build a day and start its REPL on the input, which defaults to the day's `input.txt`,
+/// with the terminal handed over to it until it exits.
+pub fn repl(root: &Path, day: u32, input: Option<&str>) -> io::Result<ExitStatus> {
+    if !run_all::build(root, day, &Options::default()) {
+        return Err(io::Error::other(format!("day {day} failed to build")));
+    }
+    let input = input.map_or_else(
+        || run_all::day_dir(root, day).join("input.txt"),
+        PathBuf::from,
+    );
+    Command::new(run_all::binary(root, day))
+        .arg("repl")
+        .arg(input)
+        .status()
+}
@@ -0,0 +1,428 @@
+//! Run every day's solutions against its `input.txt`, and check the answers against
+//! the ones stored in `answers.json` at the root of the repository.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+
+use common::answer::Answer;
+use serde_json::Value;
+
+use crate::runner::{self, Exit, Limits};
+
+/// The known answers, by day and then by part.
+pub(crate) type Answers = BTreeMap<String, BTreeMap<String, Answer>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    /// The answer matches the stored one.
+    Ok,
+
+    /// The answer differs from the stored one.
+    Wrong,
+
+    /// There's no answer stored, so there's nothing to check against.
+    Unverified,
+
+    /// The solution is a `todo!()`, or it couldn't read its input and there's no answer
+    /// stored for it yet.
+    Unimplemented,
+
+    /// The solution panicked, or its process died without reporting anything.
+    Panicked,
+
+    /// The solution didn't build, or couldn't read or parse its input.
+    Error,
+
+    /// The solution ran past the timeout, and was killed.
+    Timeout,
+
+    /// The solution ran out of memory under the limit.
+    OutOfMemory,
+}
+
+impl Status {
+    fn is_regression(self) -> bool {
+        matches!(
+            self,
+            Status::Wrong
+                | Status::Panicked
+                | Status::Error
+                | Status::Timeout
+                | Status::OutOfMemory
+        )
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Ok => "ok",
+            Status::Wrong => "wrong",
+            Status::Unverified => "unverified",
+            Status::Unimplemented => "unimplemented",
+            Status::Panicked => "panicked",
+            Status::Error => "error",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "oom",
+        };
+        f.pad(status)
+    }
+}
+
+/// How to run the solutions, chosen on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Build with the `alloc-stats` feature, and show the allocations each part made.
+    pub count_allocations: bool,
+}
+
+impl Options {
+    /// Take the `--memory` flag out of the command line arguments, leaving the positional
+    /// arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut options = Self::default();
+        if let Some(idx) = args.iter().position(|arg| arg == "--memory") {
+            args.remove(idx);
+            options.count_allocations = true;
+        }
+        options
+    }
+}
+
+pub(crate) struct Outcome {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<Answer>,
+    pub status: Status,
+    pub time: Duration,
+
+    /// The total allocations while parsing and solving, and the peak heap size,
+    /// if they were counted.
+    pub memory: Option<(u64, u64)>,
+
+    /// The `error` object of the solution's report, if it reported one.
+    pub error: Option<Value>,
+}
+
+pub(crate) fn day_dir(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("day{day}"))
+}
+
+/// The days that have a crate in the repository.
+pub(crate) fn days(root: &Path) -> Vec<u32> {
+    (1..=25)
+        .filter(|day| day_dir(root, *day).join("Cargo.toml").exists())
+        .collect()
+}
+
+pub(crate) fn load_answers(root: &Path) -> Answers {
+    load(root, "answers.json")
+}
+
+/// The answers for each day's `example.txt`, which are the ones in the puzzle descriptions.
+pub(crate) fn load_example_answers(root: &Path) -> Answers {
+    load(root, "examples.json")
+}
+
+fn load(root: &Path, file: &str) -> Answers {
+    fs::read_to_string(root.join(file))
+        .map(|content| {
+            serde_json::from_str(&content).unwrap_or_else(|e| panic!("invalid {file}: {e}"))
+        })
+        .unwrap_or_default()
+}
+
+/// The stored answer for a part, if there is one.
+pub(crate) fn expected_answer(answers: &Answers, day: u32, part: u32) -> Option<&Answer> {
+    answers
+        .get(&day.to_string())
+        .and_then(|parts| parts.get(&part.to_string()))
+}
+
+pub(crate) fn binary(root: &Path, day: u32) -> PathBuf {
+    day_dir(root, day).join(format!("target/release/day{day}"))
+}
+
+/// The command that builds the release binary for a day.
+pub(crate) fn build_command(root: &Path, day: u32, options: &Options) -> Command {
+    let mut command = Command::new("cargo");
+    command.args(["build", "--release", "--quiet"]);
+    if options.count_allocations {
+        command.args(["--features", "common/alloc-stats"]);
+    }
+    command.current_dir(day_dir(root, day));
+    command
+}
+
+/// Build the release binary for a day, returning whether it built successfully.
+pub(crate) fn build(root: &Path, day: u32, options: &Options) -> bool {
+    let status = build_command(root, day, options)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    status.is_ok_and(|status| status.success())
+}
+
+/// Build every day in parallel, returning the days that built successfully.
+fn build_all(root: &Path, days: &[u32], options: &Options) -> Vec<u32> {
+    thread::scope(|scope| {
+        let builds: Vec<_> = days
+            .iter()
+            .map(|day| scope.spawn(move || build(root, *day, options).then_some(*day)))
+            .collect();
+        builds
+            .into_iter()
+            .filter_map(|build| build.join().unwrap())
+            .collect()
+    })
+}
+
+/// Run a part in the day's directory, with the input file and any options in `args`, and
+/// check its answer against the expected one.
+pub(crate) fn run(
+    root: &Path,
+    day: u32,
+    part: u32,
+    args: &[&str],
+    expected: Option<&Answer>,
+    limits: &Limits,
+) -> Outcome {
+    let mut command = Command::new(binary(root, day));
+    command
+        .arg(part.to_string())
+        .args(args)
+        .args(["--format", "json"])
+        .current_dir(day_dir(root, day));
+    let run = runner::run(&mut command, None, limits).expect("failed to run the solution");
+    let failed = |status| Outcome {
+        day,
+        part,
+        answer: None,
+        status,
+        time: run.wall_time,
+        memory: None,
+        error: None,
+    };
+
+    let output = match &run.exit {
+        Exit::Finished(output) => output,
+        Exit::TimedOut => return failed(Status::Timeout),
+        Exit::OutOfMemory => return failed(Status::OutOfMemory),
+    };
+    let report: Option<Value> = output
+        .lines()
+        .last()
+        .and_then(|line| serde_json::from_str(line).ok());
+    let Some(report) = report else {
+        // The process died without reporting anything, e.g. from a stack overflow.
+        return failed(Status::Panicked);
+    };
+
+    if report["status"] == "ok" {
+        let answer = serde_json::from_value(report["answer"].clone())
+            .unwrap_or_else(|_| Answer::Text(String::new()));
+        let time = ["parse_time_us", "solve_time_us"]
+            .iter()
+            .map(|key| Duration::from_micros(report[key].as_u64().unwrap_or_default()))
+            .sum();
+        let status = match expected {
+            Some(expected) if expected.matches(&answer) => Status::Ok,
+            Some(_) => Status::Wrong,
+            None => Status::Unverified,
+        };
+        let memory = &report["memory"];
+        let memory = memory.is_object().then(|| {
+            let steps = [&memory["parse"], &memory["solve"]];
+            let allocations = steps.iter().filter_map(|s| s["allocations"].as_u64()).sum();
+            let peak = steps
+                .iter()
+                .filter_map(|s| s["peak_heap_bytes"].as_u64())
+                .max();
+            (allocations, peak.unwrap_or_default())
+        });
+        Outcome {
+            day,
+            part,
+            answer: Some(answer),
+            status,
+            time,
+            memory,
+            error: None,
+        }
+    } else {
+        let error = &report["error"];
+        Outcome {
+            error: Some(error.clone()),
+            ..failed(classify(error, expected))
+        }
+    }
+}
+
+/// The status of a part that reported an error instead of an answer.
+fn classify(error: &Value, expected: Option<&Answer>) -> Status {
+    match error["kind"].as_str() {
+        Some("oom") => Status::OutOfMemory,
+        Some("panic") if error["message"] == "not yet implemented" => Status::Unimplemented,
+        Some("panic") => Status::Panicked,
+        // A day that's only been stubbed out can't parse its real input yet.
+        _ if expected.is_none() => Status::Unimplemented,
+        _ => Status::Error,
+    }
+}
+
+/// Show pictures by the letters they spell out, or their line count, since they don't fit
+/// in a table row.
+pub(crate) fn display_answer(answer: Option<&Answer>) -> String {
+    answer.map_or_else(|| "-".to_string(), Answer::summary)
+}
+
+/// Show a number of bytes in the largest unit that keeps it above 1.
+fn display_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Run every part of every day under the limits, print a summary table, and return whether
+/// there were no regressions.
+pub fn run_all(root: &Path, options: &Options, limits: &Limits) -> bool {
+    let answers = load_answers(root);
+    let days = days(root);
+
+    let built = build_all(root, &days, options);
+    let mut outcomes = vec![];
+    let mut jobs = VecDeque::new();
+    for day in days.iter().copied() {
+        for part in [1, 2] {
+            if built.contains(&day) {
+                jobs.push_back((day, part));
+            } else {
+                outcomes.push(Outcome {
+                    day,
+                    part,
+                    answer: None,
+                    status: Status::Error,
+                    time: Duration::ZERO,
+                    memory: None,
+                    error: None,
+                });
+            }
+        }
+    }
+
+    // Run the solutions on a pool of worker threads, so that a slow day doesn't hold up the rest.
+    let jobs = Mutex::new(jobs);
+    let results = Mutex::new(outcomes);
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some((day, part)) = jobs.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let expected = expected_answer(&answers, day, part);
+                let outcome = run(root, day, part, &["input.txt"], expected, limits);
+                results.lock().unwrap().push(outcome);
+            });
+        }
+    });
+
+    let mut outcomes = results.into_inner().unwrap();
+    outcomes.sort_by_key(|outcome| (outcome.day, outcome.part));
+
+    let answer_width = outcomes
+        .iter()
+        .map(|outcome| display_answer(outcome.answer.as_ref()).len())
+        .max()
+        .unwrap_or_default()
+        .max("Answer".len());
+    print!(
+        "{:>3}  {:>4}  {:<answer_width$}  {:<13}  {:>10}",
+        "Day", "Part", "Answer", "Status", "Time"
+    );
+    if options.count_allocations {
+        print!("  {:>11}  {:>10}", "Allocations", "Peak heap");
+    }
+    println!();
+    for outcome in &outcomes {
+        print!(
+            "{:>3}  {:>4}  {:<answer_width$}  {:<13}  {:>8.1}ms",
+            outcome.day,
+            outcome.part,
+            display_answer(outcome.answer.as_ref()),
+            outcome.status,
+            outcome.time.as_secs_f64() * 1000.0,
+        );
+        if options.count_allocations {
+            let (allocations, peak) = match outcome.memory {
+                Some((allocations, peak)) => (allocations.to_string(), display_bytes(peak)),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            print!("  {allocations:>11}  {peak:>10}");
+        }
+        println!();
+    }
+
+    let regressions = outcomes
+        .iter()
+        .filter(|outcome| outcome.status.is_regression())
+        .count();
+    if regressions > 0 {
+        println!("\n{regressions} regression(s)");
+    }
+    regressions == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use common::answer::Answer;
+    use serde_json::json;
+
+    use super::{classify, Status};
+
+    #[test]
+    fn only_a_todo_panic_is_unimplemented() {
+        let todo = json!({ "kind": "panic", "message": "not yet implemented" });
+        assert_eq!(classify(&todo, None), Status::Unimplemented);
+        assert_eq!(
+            classify(&todo, Some(&Answer::Integer(1))),
+            Status::Unimplemented
+        );
+    }
+
+    #[test]
+    fn other_panics_fail_with_or_without_an_answer() {
+        let panic = json!({ "kind": "panic", "message": "attempt to subtract with overflow" });
+        assert_eq!(classify(&panic, None), Status::Panicked);
+        assert_eq!(
+            classify(&panic, Some(&Answer::Integer(1))),
+            Status::Panicked
+        );
+
+        let oom = json!({ "kind": "oom", "message": "memory allocation failed" });
+        assert_eq!(classify(&oom, None), Status::OutOfMemory);
+    }
+
+    #[test]
+    fn parse_errors_fail_only_once_there_is_an_answer() {
+        let parse = json!({ "kind": "parse", "message": "expected a number" });
+        assert_eq!(classify(&parse, None), Status::Unimplemented);
+        assert_eq!(classify(&parse, Some(&Answer::Integer(1))), Status::Error);
+    }
+}
@@ -0,0 +1,173 @@
+//! Running a solution in a child process of its own, with limits on how long it can run and
+//! how much memory it can use, so that a runaway search can't hang or exhaust the machine
+//! for everything else.
+
+use std::{
+    io::{self, Read, Write},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The limits each solution runs under.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// How long a solution may run before it's killed.
+    pub timeout: Duration,
+
+    /// The most memory a solution may have mapped, in bytes.
+    pub memory_bytes: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            memory_bytes: 4 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    /// Take the `--timeout <seconds>` and `--memory-limit <MiB>` options out of the command
+    /// line arguments, leaving the positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut limits = Self::default();
+        if let Some(idx) = args.iter().position(|arg| arg == "--timeout") {
+            args.remove(idx);
+            let value = args.get(idx).cloned().expect("timeout in seconds");
+            args.remove(idx);
+            limits.timeout = Duration::from_secs_f64(value.parse().expect("timeout in seconds"));
+        }
+        if let Some(idx) = args.iter().position(|arg| arg == "--memory-limit") {
+            args.remove(idx);
+            let value = args.get(idx).cloned().expect("memory limit in MiB");
+            args.remove(idx);
+            let mebibytes: u64 = value.parse().expect("memory limit in MiB");
+            limits.memory_bytes = mebibytes * 1024 * 1024;
+        }
+        limits
+    }
+}
+
+/// How a solution's process ended.
+#[derive(Debug)]
+pub enum Exit {
+    /// It exited by itself, successfully or not, printing this to stdout.
+    Finished(String),
+
+    /// It ran past the timeout, and was killed.
+    TimedOut,
+
+    /// It ran out of memory under the limit, and was aborted.
+    OutOfMemory,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub exit: Exit,
+
+    /// The time from starting the process until it exited or was killed.
+    pub wall_time: Duration,
+}
+
+/// Run a command under the limits, writing the input to its stdin if there is one.
+pub fn run(command: &mut Command, input: Option<&[u8]>, limits: &Limits) -> io::Result<Run> {
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    limit_memory(command, limits.memory_bytes);
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let (status, stdout, stderr) = thread::scope(|scope| {
+        // Write the input and read the output on their own threads, so that none of the pipes
+        // can fill up and stall the solution while the timeout is being watched.
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            scope.spawn(move || stdin.write_all(input));
+        }
+        let mut stdout = child.stdout.take().unwrap();
+        let stdout = scope.spawn(move || {
+            let mut output = String::new();
+            stdout.read_to_string(&mut output).map(|_| output)
+        });
+        let mut stderr = child.stderr.take().unwrap();
+        let stderr = scope.spawn(move || {
+            let mut output = Vec::new();
+            stderr.read_to_end(&mut output).map(|_| output)
+        });
+
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if start.elapsed() < limits.timeout => {
+                    thread::sleep(Duration::from_millis(5))
+                }
+                _ => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break None;
+                }
+            }
+        };
+        (
+            status,
+            stdout.join().unwrap().unwrap_or_default(),
+            stderr.join().unwrap().unwrap_or_default(),
+        )
+    });
+    let wall_time = start.elapsed();
+
+    let exit = match status {
+        None => Exit::TimedOut,
+        Some(status) if ran_out_of_memory(status, &stderr) => Exit::OutOfMemory,
+        Some(_) => Exit::Finished(stdout),
+    };
+    Ok(Run { exit, wall_time })
+}
+
+/// Cap the memory the process can use with `RLIMIT_DATA`, which since Linux 4.7 covers
+/// every private writable mapping, so the heap and thread stacks, but not the address
+/// space the allocator only reserves. `RLIMIT_AS` would count those reservations too, and
+/// a day with a thread per core can reserve gigabytes it never touches.
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // SAFETY: `setrlimit` is async-signal-safe, and the closure touches nothing else.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_DATA, &limit) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+/// Whether the process died from running out of memory: either an allocation failed under
+/// the limit, which Rust reports before aborting, or the kernel's OOM killer got to it first.
+fn ran_out_of_memory(status: ExitStatus, stderr: &[u8]) -> bool {
+    let allocation_failed = String::from_utf8_lossy(stderr).contains("memory allocation of");
+    #[cfg(unix)]
+    let killed = {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal() == Some(libc::SIGKILL)
+    };
+    #[cfg(not(unix))]
+    let killed = false;
+    !status.success() && (allocation_failed || killed)
+}
@@ -0,0 +1,262 @@
+//! A local HTTP service that runs the solutions, for tools that want answers without going
+//! through each day's command line.
+//!
+//! - `GET /days` lists the days, with the parts that have stored answers.
+//! - `POST /days/<day>/<part>` runs a part with the request body as its input, and responds
+//!   with the solution's JSON report, plus the wall-clock time including starting it up.
+//! - `GET /answers` and `GET /answers/<day>` return the answers stored in `answers.json`.
+//!
+//! Each day is built the first time it's asked for, and every run is a separate process
+//! with its own time and memory limits, so a solution that panics, runs past the timeout or
+//! runs out of memory can't take the service down with it.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+    path::Path,
+    process::Command,
+    sync::OnceLock,
+    thread,
+};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    run_all::{self, Answers},
+    runner::{self, Exit, Limits},
+};
+
+/// How to serve the solutions, chosen on the command line.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// The largest input accepted, in bytes.
+    pub max_input_bytes: usize,
+}
+
+impl Options {
+    /// Take the `--max-input-bytes <n>` option out of the command line arguments, leaving
+    /// the positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut options = Self {
+            max_input_bytes: 16 * 1024 * 1024,
+        };
+        if let Some(idx) = args.iter().position(|arg| arg == "--max-input-bytes") {
+            args.remove(idx);
+            let value = args.get(idx).cloned().expect("maximum input size in bytes");
+            args.remove(idx);
+            options.max_input_bytes = value.parse().expect("maximum input size in bytes");
+        }
+        options
+    }
+}
+
+struct Service<'a> {
+    root: &'a Path,
+    answers: Answers,
+    options: Options,
+    limits: Limits,
+
+    /// Whether each day built successfully, once it's been built.
+    builds: BTreeMap<u32, OnceLock<bool>>,
+}
+
+/// A response status and JSON body.
+type Reply = (u16, Value);
+
+fn error_reply(status: u16, kind: &str, message: impl Into<String>) -> Reply {
+    let message = message.into();
+    (
+        status,
+        json!({ "status": "error", "error": { "kind": kind, "message": message } }),
+    )
+}
+
+/// Listen for requests on the given address until the process is killed, handling them on a
+/// pool of worker threads, and running each solution under the limits.
+pub fn serve(root: &Path, address: &str, options: Options, limits: Limits) -> io::Result<()> {
+    let server = Server::http(address).map_err(io::Error::other)?;
+    println!("listening on http://{}", server.server_addr());
+    io::stdout().flush()?;
+
+    let service = Service {
+        root,
+        answers: run_all::load_answers(root),
+        options,
+        limits,
+        builds: run_all::days(root)
+            .into_iter()
+            .map(|day| (day, OnceLock::new()))
+            .collect(),
+    };
+
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Ok(mut request) = server.recv() {
+                    let (status, body) = service.handle(&mut request);
+                    let content_type =
+                        Header::from_bytes("Content-Type", "application/json").unwrap();
+                    let response = Response::from_string(body.to_string())
+                        .with_status_code(status)
+                        .with_header(content_type);
+                    // The client hanging up before it gets its answer is its own problem.
+                    let _ = request.respond(response);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+impl Service<'_> {
+    fn handle(&self, request: &mut Request) -> Reply {
+        let path = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match (request.method(), segments.as_slice()) {
+            (Method::Get, ["days"]) => (200, self.list_days()),
+            (Method::Get, ["answers"]) => (200, json!(self.answers)),
+            (Method::Get, ["answers", day]) => match self.day(day) {
+                Some(day) => (200, json!(self.answers.get(&day.to_string()))),
+                None => error_reply(404, "not_found", format!("there is no day {day}")),
+            },
+            (Method::Post, ["days", day, part]) => {
+                let Some(day) = self.day(day) else {
+                    return error_reply(404, "not_found", format!("there is no day {day}"));
+                };
+                let Some(part) = part
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|part| (1..=2).contains(part))
+                else {
+                    return error_reply(404, "not_found", format!("there is no part {part}"));
+                };
+                match self.read_input(request) {
+                    Ok(input) => self.run(day, part, &input),
+                    Err(reply) => reply,
+                }
+            }
+            _ => error_reply(
+                404,
+                "not_found",
+                format!("no such endpoint: {} {path}", request.method()),
+            ),
+        }
+    }
+
+    fn day(&self, day: &str) -> Option<u32> {
+        day.parse().ok().filter(|day| self.builds.contains_key(day))
+    }
+
+    fn list_days(&self) -> Value {
+        let days: Vec<Value> = self
+            .builds
+            .keys()
+            .map(|day| {
+                let answers = self.answers.get(&day.to_string());
+                let solved_parts: Vec<u32> = [1, 2]
+                    .into_iter()
+                    .filter(|part| answers.is_some_and(|a| a.contains_key(&part.to_string())))
+                    .collect();
+                json!({ "day": day, "solved_parts": solved_parts })
+            })
+            .collect();
+        json!({ "days": days })
+    }
+
+    fn read_input(&self, request: &mut Request) -> Result<Vec<u8>, Reply> {
+        let max = self.options.max_input_bytes;
+        let too_large = || {
+            error_reply(
+                413,
+                "too_large",
+                format!("inputs are limited to {max} bytes"),
+            )
+        };
+        if request.body_length().is_some_and(|length| length > max) {
+            return Err(too_large());
+        }
+
+        let mut input = Vec::new();
+        request
+            .as_reader()
+            .take(max as u64 + 1)
+            .read_to_end(&mut input)
+            .map_err(|e| error_reply(400, "io", format!("failed to read the input: {e}")))?;
+        if input.len() > max {
+            return Err(too_large());
+        }
+        Ok(input)
+    }
+
+    /// Run the solution in its own process, feeding it the input on stdin, and respond with
+    /// the report it prints.
+    fn run(&self, day: u32, part: u32, input: &[u8]) -> Reply {
+        let built = self.builds[&day]
+            .get_or_init(|| run_all::build(self.root, day, &run_all::Options::default()));
+        if !*built {
+            return error_reply(500, "build", format!("day {day} failed to build"));
+        }
+
+        let mut command = Command::new(run_all::binary(self.root, day));
+        command
+            .args([&part.to_string(), "-", "--format", "json"])
+            .current_dir(run_all::day_dir(self.root, day));
+        let run = match runner::run(&mut command, Some(input), &self.limits) {
+            Ok(run) => run,
+            Err(e) => return error_reply(500, "io", format!("failed to run day {day}: {e}")),
+        };
+        let failed = |status, kind, message: &str| {
+            let (status, mut body) = error_reply(status, kind, message);
+            body["day"] = json!(day);
+            body["part"] = json!(part);
+            (status, body)
+        };
+
+        let output = match &run.exit {
+            Exit::Finished(output) => output,
+            Exit::TimedOut => {
+                let message = format!(
+                    "the solution took longer than {:.1}s",
+                    self.limits.timeout.as_secs_f64()
+                );
+                return failed(504, "timeout", &message);
+            }
+            Exit::OutOfMemory => {
+                let message = format!(
+                    "the solution ran out of memory under the {} MiB limit",
+                    self.limits.memory_bytes / (1024 * 1024)
+                );
+                return failed(500, "oom", &message);
+            }
+        };
+
+        let report: Option<Value> = output
+            .lines()
+            .last()
+            .and_then(|line| serde_json::from_str(line).ok());
+        let Some(mut report) = report else {
+            // The process died without reporting anything, e.g. from a stack overflow.
+            return failed(
+                500,
+                "panic",
+                "the solution exited without reporting an answer",
+            );
+        };
+
+        let status = match (report["status"].as_str(), report["error"]["kind"].as_str()) {
+            (Some("ok"), _) => 200,
+            (_, Some("parse")) => 422,
+            _ => 500,
+        };
+        report["wall_time_us"] = json!(run.wall_time.as_micros() as u64);
+        (status, report)
+    }
+}
@@ -0,0 +1,220 @@
+//! Rebuilding and re-running a day whenever its source or inputs change, for working on it
+//! without switching to a terminal to run it by hand after every edit.
+
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+use crate::{
+    run_all::{self, Options, Status},
+    runner::Limits,
+};
+
+/// The files in a day's directory that its answers depend on, besides the source.
+const INPUT_FILES: &[&str] = &["Cargo.toml", "example.txt", "example.toml", "input.txt"];
+
+/// How long to wait after a change for the rest of it, since editors and `git checkout`
+/// often write several files, or the same file more than once, for a single save.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Watch a day's `src` directory and input files, and rebuild the day and run the parts on
+/// its example and real input every time they change, until interrupted.
+pub fn watch(root: &Path, day: u32, parts: &[u32], limits: &Limits) -> io::Result<()> {
+    let day_dir = run_all::day_dir(root, day);
+    let mut inotify = Inotify::init()?;
+    let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE | WatchMask::DELETE;
+    // Editors that save by writing a new file and renaming it over the old one don't
+    // modify the old file, so the directories are watched rather than the files.
+    let day_watch = inotify.watches().add(&day_dir, mask)?;
+    for dir in source_dirs(&day_dir.join("src"))? {
+        inotify.watches().add(&dir, mask)?;
+    }
+
+    let answers = run_all::load_answers(root);
+    let examples = run_all::load_example_answers(root);
+    loop {
+        check(root, day, parts, &answers, &examples, limits);
+        println!("\nwatching day {day} for changes, press Ctrl-C to stop");
+        wait_for_change(&mut inotify, &day_watch)?;
+        println!();
+    }
+}
+
+/// A directory and all of its subdirectories, since inotify only watches one level.
+fn source_dirs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut dirs = vec![dir.to_path_buf()];
+    let mut idx = 0;
+    while let Some(dir) = dirs.get(idx) {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                dirs.push(entry.path());
+            }
+        }
+        idx += 1;
+    }
+    Ok(dirs)
+}
+
+/// Block until a source or input file changes, then wait for things to settle and skip the
+/// rest of the events, so that one save leads to one run.
+fn wait_for_change(inotify: &mut Inotify, day_watch: &WatchDescriptor) -> io::Result<()> {
+    let mut buffer = [0; 4096];
+    loop {
+        let events = inotify.read_events_blocking(&mut buffer)?;
+        let relevant = events.into_iter().any(|event| {
+            let name = event.name.and_then(OsStr::to_str).unwrap_or_default();
+            is_relevant(name, event.wd == *day_watch)
+        });
+        if relevant {
+            break;
+        }
+    }
+
+    thread::sleep(SETTLE_TIME);
+    loop {
+        let skipped = match inotify.read_events(&mut buffer) {
+            Ok(events) => events.count(),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => 0,
+            Err(e) => return Err(e),
+        };
+        if skipped == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Whether a change to the named file means the day has to be rebuilt and run again, for a
+/// file directly in the day's directory or one in its sources.
+fn is_relevant(name: &str, in_day_dir: bool) -> bool {
+    if in_day_dir {
+        INPUT_FILES.contains(&name)
+    } else {
+        // Skip editors' swap and backup files.
+        name.ends_with(".rs")
+    }
+}
+
+/// Build the day, showing any compiler errors, then run each part on the example and the
+/// real input, and show how each answer compares to the expected one.
+fn check(
+    root: &Path,
+    day: u32,
+    parts: &[u32],
+    answers: &run_all::Answers,
+    examples: &run_all::Answers,
+    limits: &Limits,
+) {
+    let day_dir = run_all::day_dir(root, day);
+    println!("building day {day}");
+    let start = Instant::now();
+    let built = run_all::build_command(root, day, &Options::default())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !built {
+        println!("day {day} failed to build");
+        return;
+    }
+    println!("built in {:.1}s", start.elapsed().as_secs_f64());
+
+    let example_args: &[&str] = if day_dir.join("example.toml").exists() {
+        &["example.txt", "--config", "example.toml"]
+    } else {
+        &["example.txt"]
+    };
+    let inputs = [
+        ("example", example_args, examples),
+        ("input", &["input.txt"][..], answers),
+    ];
+
+    println!(
+        "{:<7}  {:>4}  {:<20}  {:<26}  {:>10}",
+        "Input", "Part", "Answer", "Status", "Time"
+    );
+    for (name, args, expected) in inputs {
+        if !day_dir.join(args[0]).exists() {
+            continue;
+        }
+        for part in parts.iter().copied() {
+            let expected = run_all::expected_answer(expected, day, part);
+            let outcome = run_all::run(root, day, part, args, expected, limits);
+            let status = match (outcome.status, expected) {
+                (Status::Wrong, Some(expected)) => {
+                    format!(
+                        "wrong, expected {}",
+                        run_all::display_answer(Some(expected))
+                    )
+                }
+                (status, _) => status.to_string(),
+            };
+            println!(
+                "{:<7}  {:>4}  {:<20}  {:<26}  {:>8.1}ms",
+                name,
+                part,
+                run_all::display_answer(outcome.answer.as_ref()),
+                status,
+                outcome.time.as_secs_f64() * 1000.0,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{is_relevant, source_dirs};
+
+    #[test]
+    fn reruns_for_inputs_in_the_day_directory() {
+        for name in ["Cargo.toml", "example.txt", "example.toml", "input.txt"] {
+            assert!(is_relevant(name, true), "{name}");
+        }
+        for name in ["Cargo.lock", "answers.json", "lib.rs", "notes.md", ""] {
+            assert!(!is_relevant(name, true), "{name}");
+        }
+    }
+
+    #[test]
+    fn reruns_for_rust_sources_but_not_editor_files() {
+        for name in ["lib.rs", "main.rs", "grid.rs"] {
+            assert!(is_relevant(name, false), "{name}");
+        }
+        for name in [
+            ".lib.rs.swp",
+            "lib.rs~",
+            "4913",
+            "input.txt",
+            "snapshot.snap",
+        ] {
+            assert!(!is_relevant(name, false), "{name}");
+        }
+    }
+
+    #[test]
+    fn watches_every_source_subdirectory() {
+        let src = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(src.join("grid/cells")).unwrap();
+        fs::create_dir_all(src.join("snapshots")).unwrap();
+        fs::write(src.join("lib.rs"), "").unwrap();
+
+        let mut dirs = source_dirs(&src).unwrap();
+        dirs.sort();
+        fs::remove_dir_all(&src).unwrap();
+        assert_eq!(
+            dirs,
+            [
+                src.clone(),
+                src.join("grid"),
+                src.join("grid/cells"),
+                src.join("snapshots"),
+            ]
+        );
+    }
+}
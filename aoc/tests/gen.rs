@@ -0,0 +1,113 @@
+//! Round trips through `aoc gen`: every generated input has to parse, and both parts have to
+//! solve it, when it's fed to that day's own binary.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde_json::Value;
+
+/// A file in the temporary directory, removed when the test is done with it.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("aoc-test-{}-{name}", std::process::id())))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Generate an input for a day, as `aoc gen` writes it.
+fn generate(day: u32, size: usize, seed: u64) -> TempFile {
+    let input = TempFile::new(&format!("gen-day{day}.txt"));
+    let result = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args([
+            "gen",
+            &day.to_string(),
+            &size.to_string(),
+            &seed.to_string(),
+        ])
+        .arg(&input.0)
+        .output()
+        .expect("failed to run aoc gen");
+    assert!(result.status.success(), "{result:?}");
+    input
+}
+
+/// Solve a part with the day's release binary, returning its JSON report.
+fn solve(day: u32, part: u32, input: &Path) -> Value {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let result = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(root.join(format!("day{day}/Cargo.toml")))
+        .args(["--", &part.to_string()])
+        .arg(input)
+        .args(["--format", "json"])
+        .output()
+        .expect("failed to run cargo");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let report = stdout
+        .lines()
+        .last()
+        .unwrap_or_else(|| panic!("day {day} part {part} didn't report: {result:?}"));
+    serde_json::from_str(report).unwrap()
+}
+
+fn round_trip(day: u32, unimplemented: &[u32]) {
+    for seed in [1, 2] {
+        let input = generate(day, 20, seed);
+        for part in [1, 2] {
+            let report = solve(day, part, &input.0);
+            if unimplemented.contains(&part) {
+                assert_eq!(
+                    report["error"]["message"], "not yet implemented",
+                    "{report}"
+                );
+            } else {
+                assert_eq!(report["status"], "ok", "seed {seed}: {report}");
+            }
+        }
+    }
+}
+
+macro_rules! round_trips {
+    ($($test:ident: $day:literal $(, unimplemented = $parts:expr)?;)*) => {
+        $(
+            #[test]
+            fn $test() {
+                round_trip($day, &[$($parts)?]);
+            }
+        )*
+    };
+}
+
+round_trips! {
+    day1: 1;
+    day2: 2;
+    day3: 3;
+    day4: 4;
+    day5: 5;
+    day6: 6;
+    day7: 7;
+    day8: 8;
+    day9: 9;
+    day10: 10;
+    day11: 11;
+    day12: 12;
+    day13: 13;
+    day14: 14;
+    day15: 15;
+    day16: 16;
+    day17: 17;
+    day18: 18;
+    day21: 21;
+    day24: 24;
+    day25: 25, unimplemented = 2;
+}
@@ -0,0 +1,93 @@
+//! Integration tests for `aoc minimize`, on day 1's example with an oracle that's wrong on
+//! purpose.
+
+// The oracle is a shell script.
+#![cfg(unix)]
+
+use std::{
+    env, fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// A file in the temporary directory, removed when the test is done with it.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("aoc-test-{}-{name}", std::process::id())))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// An oracle that adds up every number in the input, which only agrees with day 1's part 1
+/// when there's a single elf.
+fn sum_oracle() -> TempFile {
+    let oracle = TempFile::new("oracle.sh");
+    fs::write(
+        &oracle.0,
+        "#!/bin/sh\nawk '{ s += $1 } END { print s }' \"$1\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&oracle.0, fs::Permissions::from_mode(0o755)).unwrap();
+    oracle
+}
+
+fn minimize(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("minimize")
+        .args(args)
+        .output()
+        .expect("failed to run aoc minimize")
+}
+
+fn example() -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    root.join("day1/example.txt")
+}
+
+#[test]
+fn shrinks_a_mismatch_to_two_elves() {
+    let oracle = sum_oracle();
+    let output = TempFile::new("minimal.txt");
+    let example = example();
+    let result = minimize(&[
+        "1",
+        "1",
+        "mismatch",
+        example.to_str().unwrap(),
+        output.0.to_str().unwrap(),
+        "--oracle",
+        oracle.0.to_str().unwrap(),
+    ]);
+    assert!(result.status.success(), "{result:?}");
+
+    let minimal = fs::read_to_string(&output.0).unwrap();
+    let lines: Vec<_> = minimal.lines().collect();
+    assert_eq!(lines.len(), 3, "{minimal:?}");
+    assert!(lines[0].parse::<u32>().is_ok() && lines[2].parse::<u32>().is_ok());
+    assert_eq!(lines[1], "");
+}
+
+#[test]
+fn refuses_an_input_that_does_not_fail() {
+    let output = TempFile::new("unused.txt");
+    let example = example();
+    let result = minimize(&[
+        "1",
+        "1",
+        "panic",
+        example.to_str().unwrap(),
+        output.0.to_str().unwrap(),
+    ]);
+    assert!(!result.status.success());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("doesn't fail that way"), "{stderr}");
+    assert!(!output.0.exists());
+}
@@ -0,0 +1,166 @@
+//! Integration tests for `aoc serve`, each against its own server on a free local port.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{Child, Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+/// A running `aoc serve`, killed when the test is done with it.
+struct Server {
+    process: Child,
+    url: String,
+}
+
+impl Server {
+    fn start(options: &[&str]) -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["serve", "127.0.0.1:0"])
+            .args(options)
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start the server");
+
+        let mut line = String::new();
+        BufReader::new(process.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let url = line
+            .trim()
+            .strip_prefix("listening on ")
+            .unwrap_or_else(|| panic!("unexpected startup message: {line:?}"))
+            .to_string();
+        Self { process, url }
+    }
+
+    fn get(&self, path: &str) -> (u16, Value) {
+        respond(ureq::get(&format!("{}{path}", self.url)).call())
+    }
+
+    fn post(&self, path: &str, body: &str) -> (u16, Value) {
+        respond(ureq::post(&format!("{}{path}", self.url)).send_string(body))
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+fn respond(result: Result<ureq::Response, ureq::Error>) -> (u16, Value) {
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => panic!("request failed: {e}"),
+    };
+    let status = response.status();
+    let body = response.into_string().unwrap();
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+fn repo_file(path: &str) -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    fs::read_to_string(root.join(path)).unwrap()
+}
+
+#[test]
+fn lists_days_with_their_solved_parts() {
+    let server = Server::start(&[]);
+    let (status, body) = server.get("/days");
+    assert_eq!(status, 200);
+
+    let days = body["days"].as_array().unwrap();
+    assert_eq!(days.len(), 25);
+    assert_eq!(days[0], json!({ "day": 1, "solved_parts": [1, 2] }));
+    assert_eq!(days[18], json!({ "day": 19, "solved_parts": [] }));
+}
+
+#[test]
+fn returns_stored_answers() {
+    let server = Server::start(&[]);
+    let answers: Value = serde_json::from_str(&repo_file("answers.json")).unwrap();
+
+    assert_eq!(server.get("/answers"), (200, answers.clone()));
+    assert_eq!(server.get("/answers/13"), (200, answers["13"].clone()));
+    assert_eq!(server.get("/answers/19"), (200, Value::Null));
+}
+
+#[test]
+fn solves_posted_input() {
+    let server = Server::start(&[]);
+    let (status, body) = server.post("/days/1/1", &repo_file("day1/example.txt"));
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["day"], 1);
+    assert_eq!(body["part"], 1);
+    assert_eq!(body["answer"], 24000);
+    for timing in ["parse_time_us", "solve_time_us", "wall_time_us"] {
+        assert!(body[timing].is_u64(), "missing {timing} in {body}");
+    }
+
+    let (status, body) = server.post("/days/1/2", &repo_file("day1/input.txt"));
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["answer"], 207576);
+}
+
+#[test]
+fn reports_malformed_input() {
+    let server = Server::start(&[]);
+    let (status, body) = server.post("/days/1/1", "1000\n2000\nlots\n");
+    assert_eq!(status, 422);
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["error"]["kind"], "parse");
+    assert_eq!(body["error"]["line"], 3);
+    assert_eq!(body["error"]["found"], "lots");
+}
+
+#[test]
+fn rejects_inputs_over_the_size_limit() {
+    let server = Server::start(&["--max-input-bytes", "16"]);
+    let (status, body) = server.post("/days/1/1", "1000\n2000\n\n3000\n");
+    assert_eq!(status, 200, "{body}");
+
+    let (status, body) = server.post("/days/1/1", "1000\n2000\n\n3000\n\n4000\n");
+    assert_eq!(status, 413);
+    assert_eq!(body["error"]["kind"], "too_large");
+}
+
+#[test]
+fn stops_solutions_that_run_too_long() {
+    let server = Server::start(&["--timeout", "0.2"]);
+    let (status, body) = server.post("/days/15/2", &repo_file("day15/input.txt"));
+    assert_eq!(status, 504, "{body}");
+    assert_eq!(body["error"]["kind"], "timeout");
+    assert_eq!(body["day"], 15);
+}
+
+#[test]
+fn stops_solutions_that_run_out_of_memory() {
+    let server = Server::start(&["--memory-limit", "8"]);
+    let (status, body) = server.post("/days/1/1", &repo_file("day1/example.txt"));
+    assert_eq!(status, 200, "{body}");
+
+    let input = "1000\n".repeat(2 * 1024 * 1024);
+    let (status, body) = server.post("/days/1/1", &input);
+    assert_eq!(status, 500, "{body}");
+    assert_eq!(body["error"]["kind"], "oom");
+}
+
+#[test]
+fn rejects_unknown_endpoints() {
+    let server = Server::start(&[]);
+    for (status, body) in [
+        server.get("/nowhere"),
+        server.get("/answers/26"),
+        server.post("/days/26/1", "1\n"),
+        server.post("/days/1/3", "1\n"),
+        server.get("/days/1/1"),
+    ] {
+        assert_eq!(status, 404, "{body}");
+        assert_eq!(body["error"]["kind"], "not_found");
+    }
+}
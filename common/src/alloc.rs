@@ -0,0 +1,157 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use serde_json::{json, Value};
+
+/// Whether allocations are being counted, which is only when the `alloc-stats` feature
+/// installs [`CountingAllocator`] as the global allocator. Counting slows down
+/// allocation-heavy solutions, so it's off by default.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static HEAP_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_HEAP_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation and keeping track of the size of the
+/// heap. Reallocations count as allocations of their new size.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(allocated: usize, freed: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(allocated as u64, Ordering::Relaxed);
+        // Only the new size of a reallocation counts towards the peak, not the old block as well.
+        let heap = HEAP_BYTES.fetch_add(allocated, Ordering::Relaxed) + allocated - freed;
+        HEAP_BYTES.fetch_sub(freed, Ordering::Relaxed);
+        PEAK_HEAP_BYTES.fetch_max(heap, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        HEAP_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// The allocations made during one step of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+
+    /// The largest the heap got during the step, including anything allocated before it.
+    pub peak_heap_bytes: usize,
+}
+
+impl AllocStats {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "allocations": self.allocations,
+            "allocated_bytes": self.allocated_bytes,
+            "peak_heap_bytes": self.peak_heap_bytes,
+        })
+    }
+}
+
+/// Counts the allocations from when it's started until it's finished. Only one step
+/// should be measured at a time, since starting a measurement resets the peak heap size.
+pub struct Measurement {
+    allocations: u64,
+    allocated_bytes: u64,
+}
+
+impl Measurement {
+    pub fn start() -> Self {
+        PEAK_HEAP_BYTES.store(HEAP_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The allocations made since the measurement started, if they're being counted.
+    pub fn finish(self) -> Option<AllocStats> {
+        ENABLED.then(|| AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - self.allocated_bytes,
+            peak_heap_bytes: PEAK_HEAP_BYTES.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters() -> (u64, u64, usize) {
+        (
+            ALLOCATIONS.load(Ordering::Relaxed),
+            ALLOCATED_BYTES.load(Ordering::Relaxed),
+            HEAP_BYTES.load(Ordering::Relaxed),
+        )
+    }
+
+    #[test]
+    #[cfg_attr(
+        feature = "alloc-stats",
+        ignore = "the other tests' allocations are counted too while it runs"
+    )]
+    fn counts_an_allocation_until_it_is_freed() {
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+        let (allocations, allocated_bytes, heap_bytes) = counters();
+        PEAK_HEAP_BYTES.store(heap_bytes, Ordering::Relaxed);
+
+        let ptr = unsafe { CountingAllocator.alloc(layout) };
+        assert!(!ptr.is_null());
+        assert_eq!(
+            counters(),
+            (allocations + 1, allocated_bytes + 4096, heap_bytes + 4096)
+        );
+
+        let ptr = unsafe { CountingAllocator.realloc(ptr, layout, 8192) };
+        assert!(!ptr.is_null());
+        assert_eq!(
+            counters(),
+            (allocations + 2, allocated_bytes + 12288, heap_bytes + 8192)
+        );
+
+        unsafe { CountingAllocator.dealloc(ptr, Layout::from_size_align(8192, 8).unwrap()) };
+        assert_eq!(
+            counters(),
+            (allocations + 2, allocated_bytes + 12288, heap_bytes)
+        );
+        assert_eq!(PEAK_HEAP_BYTES.load(Ordering::Relaxed), heap_bytes + 8192);
+    }
+}
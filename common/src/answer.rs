@@ -0,0 +1,294 @@
+//! Puzzle answers, which are usually a number, sometimes a line of text like day 5's crates,
+//! and occasionally a picture like day 10's screen, which spells out letters.
+//!
+//! Every day reports its answer as an [`Answer`], and the stored answers it's checked against
+//! are read as one, so they're compared the same way whatever the day's solution returns.
+
+use std::fmt::{self, Display};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The pixel drawn for a lit pixel in an [`Answer::Art`].
+const LIT: char = '#';
+
+/// The pixel drawn for a dark pixel in an [`Answer::Art`].
+const DARK: char = '.';
+
+/// The height of the letters that picture answers spell out.
+const LETTER_HEIGHT: usize = 6;
+
+/// The width of a letter, plus the dark column between it and the next one.
+const LETTER_WIDTH: usize = 5;
+
+/// The letters that picture answers are drawn with, a row of four pixels at a time.
+const LETTERS: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// A part's answer, normalised so that two answers that mean the same are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+
+    /// A single line of text, or an integer too large for an `i128`, without surrounding
+    /// whitespace.
+    Text(String),
+
+    /// A picture of several lines, with lit pixels drawn as `#` and dark ones as `.`, whatever
+    /// the solution drew them with, and every line the same width.
+    Art(Vec<String>),
+}
+
+impl Answer {
+    /// Classify and normalise an answer as printed by a solution: several lines are a
+    /// picture, and a single line is an integer if it can be one, or text otherwise.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim_matches(['\r', '\n']);
+        if text.contains('\n') {
+            return Self::art(text.lines());
+        }
+        let text = text.trim();
+        match text.parse() {
+            Ok(integer) => Answer::Integer(integer),
+            Err(_) => Answer::Text(text.to_string()),
+        }
+    }
+
+    /// The answer a solution returned, from how it displays it.
+    pub fn from_display(answer: impl Display) -> Self {
+        Self::parse(&answer.to_string())
+    }
+
+    /// A picture from its lines. `#` and `█` are lit pixels, and anything else is dark,
+    /// including the spaces some solutions draw with, so short lines are padded with dark
+    /// pixels to the width of the longest.
+    pub fn art<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut lines: Vec<String> = lines
+            .into_iter()
+            .map(|line| {
+                let line = line.trim_end_matches('\r');
+                let pixels = line.chars().map(|c| match c {
+                    LIT | '█' => LIT,
+                    _ => DARK,
+                });
+                pixels.collect()
+            })
+            .collect();
+        let width = lines
+            .iter()
+            .map(|line| line.len())
+            .max()
+            .unwrap_or_default();
+        for line in &mut lines {
+            line.extend(std::iter::repeat_n(DARK, width - line.len()));
+        }
+        Answer::Art(lines)
+    }
+
+    /// The letters a picture spells out, if it's made up of nothing but letters in the font
+    /// the puzzles use.
+    pub fn ocr(&self) -> Option<String> {
+        let Answer::Art(lines) = self else {
+            return None;
+        };
+        let width = lines.first()?.len();
+        // The last letter's dark column is often left out.
+        if lines.len() != LETTER_HEIGHT || width == 0 || (width + 1) % LETTER_WIDTH > 1 {
+            return None;
+        }
+        (0..width.div_ceil(LETTER_WIDTH))
+            .map(|letter| {
+                let start = letter * LETTER_WIDTH;
+                let rows = lines
+                    .iter()
+                    .map(|line| &line[start..start + LETTER_WIDTH - 1]);
+                let mut spacing = lines
+                    .iter()
+                    .filter_map(|line| line.as_bytes().get(start + 4));
+                if spacing.any(|&pixel| pixel != DARK as u8) {
+                    return None;
+                }
+                LETTERS
+                    .iter()
+                    .find(|(_, glyph)| rows.clone().eq(glyph.iter().copied()))
+                    .map(|&(letter, _)| letter)
+            })
+            .collect()
+    }
+
+    /// Whether this answer is the same as another, counting a picture as the same as the
+    /// letters it spells out.
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Art(_), Answer::Text(text)) => self.ocr().as_ref() == Some(text),
+            (Answer::Text(text), Answer::Art(_)) => other.ocr().as_ref() == Some(text),
+            _ => self == other,
+        }
+    }
+
+    /// A picture's size, or the answer itself, for tables where each answer has one line.
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Art(lines) => match self.ocr() {
+                Some(letters) => letters,
+                None => format!("({} lines)", lines.len()),
+            },
+            answer => answer.to_string(),
+        }
+    }
+}
+
+/// The answer as the solution would print it, with a picture's lines separated by newlines.
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{integer}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Art(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// Integers are written as numbers if they fit in 64 bits, and as strings otherwise, since
+/// neither JSON readers nor TOML can be relied on for larger ones. Text is a string, and a
+/// picture is an array of its lines.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(integer) => match (i64::try_from(*integer), u64::try_from(*integer)) {
+                (Ok(integer), _) => serializer.serialize_i64(integer),
+                (_, Ok(integer)) => serializer.serialize_u64(integer),
+                _ => serializer.serialize_str(&integer.to_string()),
+            },
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Art(lines) => lines.serialize(serializer),
+        }
+    }
+}
+
+/// Strings are classified like a solution's output, so an integer stored as a string, or a
+/// picture stored as a single string of lines, is the same as one stored the usual way.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an integer, a string or an array of lines")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(Answer::Integer(value.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(Answer::Integer(value.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Answer, E> {
+        Ok(Answer::parse(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Answer, A::Error> {
+        let mut lines = vec![];
+        while let Some(line) = seq.next_element::<String>()? {
+            lines.push(line);
+        }
+        Ok(Answer::art(lines.iter().map(String::as_str)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    const SCREEN: &str = "\
+####.####.####..##..#..#...##..##..###..
+#.......#.#....#..#.#..#....#.#..#.#..#.
+###....#..###..#....####....#.#..#.###..
+#.....#...#....#....#..#....#.####.#..#.
+#....#....#....#..#.#..#.#..#.#..#.#..#.
+####.####.#.....##..#..#..##..#..#.###..
+";
+
+    #[test]
+    fn classifies_and_normalises_answers() {
+        assert_eq!(Answer::parse(" 24000\n"), Answer::Integer(24000));
+        assert_eq!(Answer::from_display(-3_i64), Answer::Integer(-3));
+        assert_eq!(Answer::parse("CMZ\r\n"), Answer::Text("CMZ".to_string()));
+        assert_eq!(Answer::parse("2=-1=0"), Answer::Text("2=-1=0".to_string()));
+
+        let art = Answer::parse("█ █\n█\n");
+        assert_eq!(art, Answer::Art(vec!["#.#".to_string(), "#..".to_string()]));
+        assert_eq!(art.to_string(), "#.#\n#..");
+        assert_eq!(art.summary(), "(2 lines)");
+    }
+
+    #[test]
+    fn reads_the_letters_in_a_picture() {
+        let screen = Answer::parse(SCREEN);
+        assert_eq!(screen.ocr().as_deref(), Some("EZFCHJAB"));
+        assert!(screen.matches(&Answer::Text("EZFCHJAB".to_string())));
+        assert!(!screen.matches(&Answer::Text("EZFCHJAC".to_string())));
+
+        // Without the last dark column, and drawn with spaces.
+        let trimmed: Vec<_> = SCREEN
+            .lines()
+            .map(|line| line[..39].replace('.', " "))
+            .collect();
+        assert!(Answer::parse(&trimmed.join("\n")).matches(&Answer::Text("EZFCHJAB".to_string())));
+        assert_eq!(Answer::parse("#####").ocr(), None);
+    }
+
+    #[test]
+    fn serialises_to_json_and_toml() {
+        let answers = [
+            Answer::Integer(13180),
+            Answer::Integer(i128::MAX),
+            Answer::Text("CMZ".to_string()),
+            Answer::parse(SCREEN),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert!(json.starts_with(r##"[13180,"170141183460469231731687303715884105727","CMZ",["#"##));
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+
+        let answers = BTreeMap::from([
+            ("part1", Answer::Integer(13180)),
+            ("part2", Answer::parse(SCREEN)),
+        ]);
+        let toml = toml::to_string(&answers).unwrap();
+        assert!(toml.starts_with("part1 = 13180\npart2 = ["));
+        let parsed: BTreeMap<&str, Answer> = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, answers);
+
+        let stored: Answer = serde_json::from_str(r#""24000""#).unwrap();
+        assert_eq!(stored, Answer::Integer(24000));
+    }
+}
@@ -0,0 +1,226 @@
+//! Finding where a simulation starts repeating itself, so that it can be extrapolated to step
+//! counts far too large to simulate.
+//!
+//! A simulation is a state and a step function that advances it. Each state is identified by
+//! a fingerprint, which has to capture everything that affects the steps after it: two states
+//! with the same fingerprint must go on to behave the same. Along the way a metric is recorded
+//! after every step, such as the height of a tower or the number of items inspected, and once
+//! the cycle is known the metric can be worked out for any number of steps.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+use tracing::debug;
+
+/// A cycle in a simulation, along with the metric recorded at every step up to the end of the
+/// first time around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<M> {
+    /// The first step that's part of the cycle.
+    pub start: usize,
+
+    /// The number of steps it takes for the state to repeat.
+    pub length: usize,
+
+    /// The metric for the initial state and after each step, up to and including the step
+    /// where the first repeated state was reached, `start + length`.
+    pub metrics: Vec<M>,
+}
+
+impl<M> Cycle<M> {
+    /// The step within the recorded steps whose state is the same as the state after `steps`.
+    pub fn equivalent_step(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+
+    /// How many whole times the simulation goes around the cycle in `steps`, beyond the
+    /// recorded steps.
+    pub fn repeats(&self, steps: usize) -> usize {
+        (steps.max(self.start) - self.start) / self.length
+    }
+}
+
+impl<M> Cycle<M>
+where
+    M: Copy + Add<Output = M> + Sub<Output = M> + Mul<Output = M> + TryFrom<usize>,
+{
+    /// The metric after `steps`, assuming that it changes by the same amount each time around
+    /// the cycle, as a running total does.
+    ///
+    /// Panics if the number of repeats doesn't fit in the metric's type.
+    pub fn extrapolate(&self, steps: usize) -> M {
+        let equivalent = self.metrics[self.equivalent_step(steps)];
+        let per_cycle = self.metrics[self.start + self.length] - self.metrics[self.start];
+        let repeats = M::try_from(self.repeats(steps))
+            .unwrap_or_else(|_| panic!("{} repeats of the cycle is too many", self.repeats(steps)));
+        equivalent + per_cycle * repeats
+    }
+}
+
+/// Run a simulation until a state's fingerprint repeats, remembering the fingerprint of every
+/// state on the way. This takes as few steps as possible, but the fingerprints have to fit in
+/// memory, so they should be compact.
+///
+/// Returns `None` if no state has repeated after `max_steps`.
+pub fn find_cycle<S, K, M>(
+    mut state: S,
+    max_steps: usize,
+    mut step: impl FnMut(&mut S),
+    fingerprint: impl Fn(&S) -> K,
+    metric: impl Fn(&S) -> M,
+) -> Option<Cycle<M>>
+where
+    K: Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    let mut metrics = vec![];
+    for steps in 0..=max_steps {
+        metrics.push(metric(&state));
+        if let Some(start) = seen.insert(fingerprint(&state), steps) {
+            debug!(start, length = steps - start, "found cycle");
+            return Some(Cycle {
+                start,
+                length: steps - start,
+                metrics,
+            });
+        }
+        step(&mut state);
+    }
+    None
+}
+
+/// Find a cycle with Brent's algorithm, which only ever holds two states at a time, for
+/// simulations whose fingerprints are too large to remember every one of. It takes a few
+/// times as many steps as [`find_cycle`], since it has to run through the start of the
+/// simulation again to find where the cycle begins and record the metrics.
+///
+/// Returns `None` if it hasn't found the cycle's length after `max_steps`, which may be a
+/// little after the first repeated state.
+pub fn find_cycle_brent<S, K, M>(
+    initial: S,
+    max_steps: usize,
+    mut step: impl FnMut(&mut S),
+    fingerprint: impl Fn(&S) -> K,
+    metric: impl Fn(&S) -> M,
+) -> Option<Cycle<M>>
+where
+    S: Clone,
+    K: Eq,
+{
+    // Find the length of the cycle, by moving the hare ahead one step at a time and moving
+    // the tortoise up to it at each power of two, until the hare lands back on the tortoise.
+    let mut tortoise = fingerprint(&initial);
+    let mut hare = initial.clone();
+    step(&mut hare);
+    let (mut power, mut length, mut steps) = (1, 1, 1);
+    while fingerprint(&hare) != tortoise {
+        if steps == max_steps {
+            return None;
+        }
+        if power == length {
+            tortoise = fingerprint(&hare);
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // Then find where it starts, with the hare `length` steps ahead of the tortoise, by
+    // moving them both along until they reach the same state.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    let mut metrics = vec![metric(&tortoise)];
+    for _ in 0..length {
+        step(&mut hare);
+        metrics.push(metric(&hare));
+    }
+    let mut start = 0;
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        metrics.push(metric(&hare));
+        start += 1;
+    }
+    debug!(start, length, "found cycle");
+
+    Some(Cycle {
+        start,
+        length,
+        metrics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Repeatedly squaring a number modulo 1000 is a simple example with a lead-in before its
+    /// cycle, with the running total of the numbers as the metric.
+    type Squares = (u64, u64);
+
+    fn square(state: &mut Squares) {
+        let (value, total) = state;
+        *value = *value * *value % 1000;
+        *total += *value;
+    }
+
+    fn value(state: &Squares) -> u64 {
+        state.0
+    }
+
+    fn total(state: &Squares) -> u64 {
+        state.1
+    }
+
+    fn squares(max_steps: usize, brent: bool) -> Option<Cycle<u64>> {
+        if brent {
+            find_cycle_brent((7, 7), max_steps, square, value, total)
+        } else {
+            find_cycle((7, 7), max_steps, square, value, total)
+        }
+    }
+
+    fn simulate(steps: usize) -> u64 {
+        let (mut value, mut total) = (7u64, 7u64);
+        for _ in 0..steps {
+            value = value * value % 1000;
+            total += value;
+        }
+        total
+    }
+
+    #[test]
+    fn finds_the_same_cycle_either_way() {
+        let hashed = squares(1000, false).unwrap();
+        let brent = squares(1000, true).unwrap();
+        assert_eq!(hashed, brent);
+        assert_eq!((hashed.start, hashed.length), (2, 4));
+    }
+
+    #[test]
+    fn extrapolates_the_metric() {
+        let cycle = squares(1000, false).unwrap();
+        for steps in [0, 2, 3, 7, 8, 100, 1001] {
+            assert_eq!(
+                cycle.extrapolate(steps),
+                simulate(steps),
+                "after {steps} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn gives_up_after_the_step_limit() {
+        assert_eq!(squares(5, false), None);
+        assert_eq!(squares(5, true), None);
+    }
+}
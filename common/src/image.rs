@@ -0,0 +1,271 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter},
+};
+
+/// A colour, as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+/// The colour of empty space, and of the parts of the picture outside a ragged grid.
+pub const BACKGROUND: Rgb = [16, 16, 32];
+
+/// Grids are scaled up so their larger side is about this many pixels, if they're small.
+const TARGET_SIZE: usize = 800;
+
+/// The most pixels wide and tall a single cell is drawn.
+const MAX_SCALE: usize = 8;
+
+/// Where to write a picture of the puzzle, chosen on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngExport {
+    pub path: String,
+}
+
+impl PngExport {
+    /// Take the `--png <file>` option out of the command line arguments, leaving the
+    /// positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Option<Self> {
+        let idx = args.iter().position(|arg| arg == "--png")?;
+        args.remove(idx);
+        let path = args.get(idx).cloned().expect("image file");
+        args.remove(idx);
+        Some(Self { path })
+    }
+
+    pub fn write<T, R: AsRef<[T]>>(&self, grid: &[R], color: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        write_png(&self.path, grid, color)
+    }
+}
+
+/// The cells of a grid drawn as ASCII text, one row per line.
+pub fn text_grid(text: &str) -> Vec<&[u8]> {
+    text.lines().map(str::as_bytes).collect()
+}
+
+/// Colours for the characters the solutions draw their grids with. Walls and rock are grey,
+/// sand is yellow, the things being simulated are red, and anything else gets a colour of
+/// its own.
+pub fn text_color(tile: &u8) -> Rgb {
+    match *tile as char {
+        '.' | ' ' => BACKGROUND,
+        '#' => [150, 150, 160],
+        'o' => [230, 190, 90],
+        '+' | 's' => [90, 200, 90],
+        'E' | 'H' | 'T' | '@' => [230, 70, 60],
+        '[' | ']' => [110, 80, 50],
+        '^' | 'v' | '<' | '>' => [110, 170, 240],
+        digit if digit.is_ascii_digit() => {
+            let shade = 255 - 14 * digit.to_digit(10).unwrap() as u8;
+            [shade, shade / 3, shade / 4]
+        }
+        other => {
+            // Spread the remaining characters around the colour wheel.
+            heat(((other as u32).wrapping_mul(37) % 101) as f64 / 100.0)
+        }
+    }
+}
+
+/// A colour from a dark blue through green to yellow, for values scaled to the `0..=1` range.
+pub fn heat(fraction: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [
+        [40, 20, 90],
+        [40, 90, 140],
+        [30, 150, 130],
+        [120, 200, 70],
+        [250, 230, 40],
+    ];
+
+    let position = fraction.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let idx = (position.floor() as usize).min(STOPS.len() - 2);
+    let t = position - idx as f64;
+    let (from, to) = (STOPS[idx], STOPS[idx + 1]);
+    [0, 1, 2].map(|c| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8)
+}
+
+fn grid_size<T, R: AsRef<[T]>>(grid: &[R]) -> (usize, usize) {
+    let width = grid
+        .iter()
+        .map(|row| row.as_ref().len())
+        .max()
+        .unwrap_or_default();
+    (width, grid.len())
+}
+
+fn scale_for(width: usize, height: usize) -> usize {
+    (TARGET_SIZE / width.max(height).max(1)).clamp(1, MAX_SCALE)
+}
+
+/// Draw the grid to a PNG file, with each cell coloured by the given mapping.
+pub fn write_png<T, R: AsRef<[T]>>(
+    path: &str,
+    grid: &[R],
+    color: impl Fn(&T) -> Rgb,
+) -> io::Result<()> {
+    let (width, height) = grid_size(grid);
+    let scale = scale_for(width, height);
+    let (image_width, image_height) = (width.max(1) * scale, height.max(1) * scale);
+
+    let mut pixels = Vec::with_capacity(image_width * image_height * 3);
+    for pixel_row in 0..image_height {
+        let row = grid.get(pixel_row / scale);
+        for pixel_column in 0..image_width {
+            let cell = row.and_then(|row| row.as_ref().get(pixel_column / scale));
+            pixels.extend(cell.map_or(BACKGROUND, &color));
+        }
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, image_width as u32, image_height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&pixels).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Draw each grid as a frame of an animated GIF, which loops forever. Frames of different
+/// sizes are drawn from the top left corner, on a background big enough for all of them.
+pub fn write_gif<T, R: AsRef<[T]>, G: AsRef<[R]>>(
+    path: &str,
+    frames: &[G],
+    color: impl Fn(&T) -> Rgb,
+    frame_delay_ms: u16,
+) -> io::Result<()> {
+    let (width, height) = frames
+        .iter()
+        .map(|grid| grid_size(grid.as_ref()))
+        .fold((1, 1), |(w, h), (fw, fh)| (w.max(fw), h.max(fh)));
+    let scale = scale_for(width, height);
+    let (image_width, image_height) = (width * scale, height * scale);
+    if image_width > u16::MAX as usize || image_height > u16::MAX as usize {
+        return Err(io::Error::other(format!(
+            "{image_width}x{image_height} is too large for a GIF"
+        )));
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, image_width as u16, image_height as u16, &[])
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for grid in frames {
+        let grid = grid.as_ref();
+        // GIF frames have at most 256 colours, so each distinct colour gets an index into
+        // a palette for the frame.
+        let mut palette: HashMap<Rgb, u8> = HashMap::new();
+        let mut palette_order: Vec<Rgb> = Vec::new();
+        let mut indices = Vec::with_capacity(image_width * image_height);
+        for pixel_row in 0..image_height {
+            let row = grid.get(pixel_row / scale);
+            for pixel_column in 0..image_width {
+                let cell = row.and_then(|row| row.as_ref().get(pixel_column / scale));
+                let rgb = cell.map_or(BACKGROUND, &color);
+                let index = match palette.get(&rgb) {
+                    Some(index) => *index,
+                    None if palette_order.len() < 256 => {
+                        let index = palette_order.len() as u8;
+                        palette.insert(rgb, index);
+                        palette_order.push(rgb);
+                        index
+                    }
+                    None => return Err(io::Error::other("more than 256 colours in a frame")),
+                };
+                indices.push(index);
+            }
+        }
+
+        let mut frame =
+            gif::Frame::from_indexed_pixels(image_width as u16, image_height as u16, indices, None);
+        frame.palette = Some(palette_order.concat());
+        frame.delay = frame_delay_ms / 10;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::*;
+
+    /// An image in the temporary directory, deleted when dropped.
+    struct ImageFile(PathBuf);
+
+    impl ImageFile {
+        fn new(name: &str) -> Self {
+            Self(env::temp_dir().join(format!("aoc-image-{}-{name}", process::id())))
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for ImageFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    const SAND: Rgb = [230, 190, 90];
+    const ROCK: Rgb = [150, 150, 160];
+
+    /// The colour of the pixel in an RGB or RGBA image.
+    fn pixel(pixels: &[u8], image_width: usize, channels: usize, x: usize, y: usize) -> Rgb {
+        let idx = (y * image_width + x) * channels;
+        [pixels[idx], pixels[idx + 1], pixels[idx + 2]]
+    }
+
+    #[test]
+    fn png_decodes_to_the_scaled_up_grid() {
+        let image = ImageFile::new("grid.png");
+        // The second row is short, so its last cell is drawn as the background.
+        write_png(image.path(), &text_grid("#o#\no#"), text_color).unwrap();
+
+        let decoder = png::Decoder::new(File::open(&image.0).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (24, 16));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+
+        let cell = |column: usize, row: usize| pixel(&pixels, 24, 3, column * 8 + 7, row * 8);
+        assert_eq!([cell(0, 0), cell(1, 0), cell(2, 0)], [ROCK, SAND, ROCK]);
+        assert_eq!(
+            [cell(0, 1), cell(1, 1), cell(2, 1)],
+            [SAND, ROCK, BACKGROUND]
+        );
+    }
+
+    #[test]
+    fn gif_decodes_to_a_frame_per_grid() {
+        let image = ImageFile::new("frames.gif");
+        let frames = [text_grid("o."), text_grid("..\n.o")];
+        write_gif(image.path(), &frames, text_color, 200).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&image.0).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (16, 16));
+
+        let mut decoded = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (16, 16, 20));
+            let cell = |column: usize, row: usize| {
+                pixel(&frame.buffer, 16, 4, column * 8 + 7, row * 8 + 7)
+            };
+            decoded.push([cell(0, 0), cell(1, 0), cell(0, 1), cell(1, 1)]);
+        }
+        assert_eq!(
+            decoded,
+            [
+                [SAND, BACKGROUND, BACKGROUND, BACKGROUND],
+                [BACKGROUND, BACKGROUND, BACKGROUND, SAND],
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Puzzle inputs the way every parser expects them, whatever editor or operating system they
+//! were saved with.
+//!
+//! [`crate::report::Report::read_input`] normalises the whole input before it's parsed, and
+//! the parsers split it with [`lines`] and [`paragraphs`], which also cope with text that
+//! hasn't been normalised, like an example pasted into a test.
+
+/// The byte order mark some Windows editors put at the start of a UTF-8 file.
+pub(crate) const BOM: char = '\u{feff}';
+
+/// Strip a byte order mark, turn `\r\n` and lone `\r` line endings into `\n`, remove the
+/// whitespace at the end of each line and the blank lines at the end of the input, and end
+/// the last line with a `\n`. Whitespace at the start of a line is significant on some days,
+/// like day 22's map, so it's kept.
+pub fn normalize(text: &str) -> String {
+    let text = text.strip_prefix(BOM).unwrap_or(text);
+    let mut normalized = String::with_capacity(text.len() + 1);
+    let lines = text
+        .split('\n')
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split('\r'));
+    for line in lines {
+        normalized += line.trim_end();
+        normalized += "\n";
+    }
+    let end = normalized.trim_end().len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized += "\n";
+    }
+    normalized
+}
+
+/// The lines of a text, without their line endings or trailing whitespace, or the blank
+/// lines at the end of the text. An empty text is a single empty line, so that a parser
+/// expecting at least one line reports it as missing.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.trim_end().split('\n').map(str::trim_end)
+}
+
+/// The paragraphs of a text, which are separated by blank lines. Each one runs from the
+/// start of its first line to the end of its last, without the trailing whitespace, so
+/// [`lines`] splits it up further. An empty text is a single empty paragraph.
+pub fn paragraphs(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text.trim_end());
+    std::iter::from_fn(move || {
+        let remaining = rest?;
+        let mut end = 0;
+        let mut blank_lines = None;
+        for line in remaining.split('\n') {
+            let is_blank = line.trim().is_empty();
+            match blank_lines {
+                // The first blank line after the paragraph, which ends it.
+                None if is_blank && end > 0 => blank_lines = Some((end, end + line.len() + 1)),
+                None => end += line.len() + 1,
+                Some((_, ref mut next)) if is_blank => *next += line.len() + 1,
+                Some((end, next)) => {
+                    rest = Some(&remaining[next..]);
+                    return Some(remaining[..end].trim_end());
+                }
+            }
+        }
+        rest = None;
+        Some(remaining)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_and_whitespace() {
+        let text = "\u{feff}  a \r\nb\t\rc\n\r\n  \n";
+        assert_eq!(normalize(text), "  a\nb\nc\n");
+        assert_eq!(normalize("a\n\nb"), "a\n\nb\n");
+        assert_eq!(normalize("\r\n \n"), "");
+    }
+
+    #[test]
+    fn splits_lines_and_paragraphs() {
+        let text = "1 \r\n2\r\n \r\n\r\n3\r\n\r\n";
+        assert_eq!(lines(text).collect::<Vec<_>>(), ["1", "2", "", "", "3"]);
+
+        let split: Vec<_> = paragraphs(text).collect();
+        assert_eq!(split, ["1 \r\n2", "3"]);
+        assert_eq!(lines(split[0]).collect::<Vec<_>>(), ["1", "2"]);
+        assert_eq!(
+            paragraphs("a\n\n\n  b\nc").collect::<Vec<_>>(),
+            ["a", "  b\nc"]
+        );
+
+        assert_eq!(lines("").collect::<Vec<_>>(), [""]);
+        assert_eq!(paragraphs("").collect::<Vec<_>>(), [""]);
+    }
+}
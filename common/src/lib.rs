@@ -0,0 +1,23 @@
+//! Helpers shared between the solutions for each day.
+
+pub mod alloc;
+pub mod answer;
+pub mod cycle;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod input;
+pub mod logging;
+pub mod num;
+pub mod params;
+pub mod parse;
+pub mod repl;
+pub mod report;
+pub mod solution;
+pub mod stream;
+pub mod timeline;
+
+#[cfg(feature = "visualize")]
+pub mod visualize;
+
+#[cfg(feature = "macros")]
+pub use macros::{aoc, aoc_parser};
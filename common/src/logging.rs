@@ -0,0 +1,33 @@
+use std::io::{self, IsTerminal};
+
+use tracing::Level;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Take the `-v`, `-vv` and `-vvv` flags out of the command line arguments, and send
+/// tracing output to stderr at the matching level, so that stdout only has the answer.
+///
+/// By default only warnings and errors are shown. `-v` adds how long parsing and each part
+/// took, `-vv` adds the individual simulation steps, and `-vvv` adds the full simulation state.
+pub fn init_from_args(args: &mut Vec<String>) {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        let is_flag = arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v');
+        if is_flag {
+            verbosity += arg.len() - 1;
+        }
+        !is_flag
+    });
+
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
+}
@@ -0,0 +1,291 @@
+//! Integers with checked arithmetic, for the solutions whose values can grow past `i64` on
+//! larger inputs. Those solutions are generic over [`Int`], and the type they run with is
+//! chosen on the command line, so an overflow is reported as an error instead of wrapping
+//! around silently in a release build.
+
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
+/// The integer type to run a solution with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I64,
+    I128,
+
+    /// Arbitrary precision, which can't overflow, but is a lot slower.
+    #[cfg(feature = "bigint")]
+    Big,
+}
+
+impl IntType {
+    /// Take the `--int <i64|i128|big>` option out of the command line arguments, leaving the
+    /// positional arguments in place. Solutions run with `i64` unless told otherwise.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let Some(idx) = args.iter().position(|arg| arg == "--int") else {
+            return IntType::I64;
+        };
+        args.remove(idx);
+        let int_type = args.get(idx).cloned().expect("integer type");
+        args.remove(idx);
+        match int_type.as_str() {
+            "i64" => IntType::I64,
+            "i128" => IntType::I128,
+            #[cfg(feature = "bigint")]
+            "big" => IntType::Big,
+            _ => unreachable!("{}", int_type),
+        }
+    }
+}
+
+/// Evaluate an expression with `$n` standing for the integer type chosen at run time. This is
+/// how `#[aoc]` runs a solution that's generic over [`Int`].
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "bigint")]
+macro_rules! __with_int_type {
+    ($int_type:expr, $n:ident => $body:expr) => {
+        match $int_type {
+            $crate::num::IntType::I64 => {
+                type $n = i64;
+                $body
+            }
+            $crate::num::IntType::I128 => {
+                type $n = i128;
+                $body
+            }
+            $crate::num::IntType::Big => {
+                type $n = $crate::num::BigInt;
+                $body
+            }
+        }
+    };
+}
+
+/// Evaluate an expression with `$n` standing for the integer type chosen at run time. This is
+/// how `#[aoc]` runs a solution that's generic over [`Int`].
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "bigint"))]
+macro_rules! __with_int_type {
+    ($int_type:expr, $n:ident => $body:expr) => {
+        match $int_type {
+            $crate::num::IntType::I64 => {
+                type $n = i64;
+                $body
+            }
+            $crate::num::IntType::I128 => {
+                type $n = i128;
+                $body
+            }
+        }
+    };
+}
+
+/// Why a calculation has no result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result is too large for the integer type.
+    Overflow {
+        expression: String,
+        int_type: &'static str,
+    },
+
+    DivisionByZero {
+        expression: String,
+    },
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticError::Overflow {
+                expression,
+                int_type,
+            } => write!(f, "{expression} overflows {int_type}"),
+            ArithmeticError::DivisionByZero { expression } => {
+                write!(f, "division by zero in {expression}")
+            }
+        }
+    }
+}
+
+impl Error for ArithmeticError {}
+
+/// An integer type that reports overflow instead of wrapping or panicking.
+pub trait Int: Clone + Debug + Display + Ord + Hash {
+    /// The name of the type, for error messages.
+    const NAME: &'static str;
+
+    fn from_i64(value: i64) -> Self;
+
+    /// The value as an `i64`, if it fits in one.
+    fn to_i64(&self) -> Option<i64>;
+
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    /// Division, rounding towards zero like the built-in integers do.
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    /// The remainder of [`Int::checked_div`], with the same sign as `self`.
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::from_i64(0)
+    }
+}
+
+fn overflow<N: Int>(lhs: &N, operator: &str, rhs: &N) -> ArithmeticError {
+    ArithmeticError::Overflow {
+        expression: format!("{lhs} {operator} {rhs}"),
+        int_type: N::NAME,
+    }
+}
+
+fn division_by_zero<N: Int>(lhs: &N, operator: &str, rhs: &N) -> ArithmeticError {
+    ArithmeticError::DivisionByZero {
+        expression: format!("{lhs} {operator} {rhs}"),
+    }
+}
+
+macro_rules! impl_int {
+    ($int:ty) => {
+        impl Int for $int {
+            const NAME: &'static str = stringify!($int);
+
+            fn from_i64(value: i64) -> Self {
+                value.into()
+            }
+
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+
+            fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+                <$int>::checked_add(*self, *rhs).ok_or_else(|| overflow(self, "+", rhs))
+            }
+
+            fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+                <$int>::checked_sub(*self, *rhs).ok_or_else(|| overflow(self, "-", rhs))
+            }
+
+            fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+                <$int>::checked_mul(*self, *rhs).ok_or_else(|| overflow(self, "*", rhs))
+            }
+
+            fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+                if *rhs == 0 {
+                    return Err(division_by_zero(self, "/", rhs));
+                }
+                <$int>::checked_div(*self, *rhs).ok_or_else(|| overflow(self, "/", rhs))
+            }
+
+            fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+                if *rhs == 0 {
+                    return Err(division_by_zero(self, "%", rhs));
+                }
+                <$int>::checked_rem(*self, *rhs).ok_or_else(|| overflow(self, "%", rhs))
+            }
+        }
+    };
+}
+
+impl_int!(i64);
+impl_int!(i128);
+
+#[cfg(feature = "bigint")]
+impl Int for BigInt {
+    const NAME: &'static str = "BigInt";
+
+    fn from_i64(value: i64) -> Self {
+        value.into()
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        self.try_into().ok()
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(self * rhs)
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(division_by_zero(self, "/", rhs));
+        }
+        Ok(self / rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(division_by_zero(self, "%", rhs));
+        }
+        Ok(self % rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overflow_with_the_expression_and_type() {
+        let error = Int::checked_add(&i64::MAX, &1).unwrap_err();
+        assert_eq!(error.to_string(), "9223372036854775807 + 1 overflows i64");
+        assert_eq!(Int::checked_add(&(i64::MAX as i128), &1), Ok(1 << 63));
+
+        assert!(Int::checked_sub(&i64::MIN, &1).is_err());
+        assert!(Int::checked_mul(&(1i64 << 32), &(1 << 31)).is_err());
+        assert_eq!(Int::checked_mul(&(1i128 << 32), &(1 << 31)), Ok(1 << 63));
+
+        // The one division that overflows: the result is one past the largest value.
+        let error = Int::checked_div(&i64::MIN, &-1).unwrap_err();
+        assert!(matches!(
+            error,
+            ArithmeticError::Overflow {
+                int_type: "i64",
+                ..
+            }
+        ));
+        assert!(Int::checked_rem(&i128::MIN, &-1).is_err());
+    }
+
+    #[test]
+    fn reports_division_by_zero() {
+        let error = Int::checked_div(&7i64, &0).unwrap_err();
+        assert_eq!(error.to_string(), "division by zero in 7 / 0");
+        let error = Int::checked_rem(&7i128, &0).unwrap_err();
+        assert_eq!(error.to_string(), "division by zero in 7 % 0");
+
+        assert_eq!(Int::checked_div(&-7i64, &2), Ok(-3));
+        assert_eq!(Int::checked_rem(&-7i64, &2), Ok(-1));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_integers_only_fail_on_division_by_zero() {
+        let max = BigInt::from_i64(i64::MAX);
+        let square = max.checked_mul(&max).unwrap();
+        assert_eq!(square.to_string(), "85070591730234615847396907784232501249");
+        assert_eq!(square.to_i64(), None);
+        assert_eq!(square.checked_div(&max).unwrap().to_i64(), Some(i64::MAX));
+
+        let error = max.checked_rem(&BigInt::from_i64(0)).unwrap_err();
+        assert!(matches!(error, ArithmeticError::DivisionByZero { .. }));
+    }
+}
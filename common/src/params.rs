@@ -0,0 +1,214 @@
+use std::fs;
+
+use toml::{Table, Value};
+
+/// Puzzle parameters given on the command line, like the row to check on day 15 or the
+/// number of rounds on day 11. Each day declares the parameters it uses, with a default
+/// for the real puzzle, through [`crate::report::Report::param`].
+///
+/// Values given with `--param name=value` take priority over ones in the `--config` file,
+/// which is a TOML table of the parameters' values.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    overrides: Vec<(String, String)>,
+    config_file: Option<String>,
+}
+
+/// Why a parameter couldn't be looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub name: String,
+    pub message: String,
+}
+
+impl Params {
+    /// Take the `--param name=value` and `--config <file>` options out of the command line
+    /// arguments, leaving the positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut params = Self::default();
+        while let Some(idx) = args.iter().position(|arg| arg == "--param") {
+            args.remove(idx);
+            let param = args.get(idx).cloned().expect("parameter");
+            args.remove(idx);
+            let (name, value) = param.split_once('=').expect("parameter as name=value");
+            params
+                .overrides
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+        if let Some(idx) = args.iter().position(|arg| arg == "--config") {
+            args.remove(idx);
+            params.config_file = Some(args.get(idx).cloned().expect("config file"));
+            args.remove(idx);
+        }
+        params
+    }
+
+    /// Check that every parameter given is one of the day's, so that a misspelt name is an
+    /// error rather than quietly running with the default.
+    pub fn check(&self, declared: &[&str]) -> Result<(), ParamError> {
+        let config = self.config()?;
+        let names = self.overrides.iter().map(|(name, _)| name);
+        for name in names.chain(config.keys()) {
+            if !declared.contains(&name.as_str()) {
+                let message = match declared {
+                    [] => format!("unknown parameter {name}: this day has no parameters"),
+                    _ => format!(
+                        "unknown parameter {name}: expected one of {}",
+                        declared.join(", ")
+                    ),
+                };
+                return Err(ParamError {
+                    name: name.clone(),
+                    message,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the value given for a parameter, if there is one.
+    pub fn lookup(&self, name: &str) -> Result<Option<String>, ParamError> {
+        if let Some((_, value)) = self.overrides.iter().rev().find(|(key, _)| key == name) {
+            return Ok(Some(value.clone()));
+        }
+
+        let path = self.config_file.as_deref().unwrap_or_default();
+        match self.config()?.get(name) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(value @ (Value::Integer(_) | Value::Float(_) | Value::Boolean(_))) => {
+                Ok(Some(value.to_string()))
+            }
+            Some(value) => Err(ParamError {
+                name: name.to_string(),
+                message: format!(
+                    "{path}: expected a number, string or boolean for {name}, found {value}"
+                ),
+            }),
+        }
+    }
+
+    /// The parameters in the config file, or none without one.
+    fn config(&self) -> Result<Table, ParamError> {
+        let Some(path) = &self.config_file else {
+            return Ok(Table::new());
+        };
+        let error = |message: String| ParamError {
+            name: String::new(),
+            message,
+        };
+        let content =
+            fs::read_to_string(path).map_err(|e| error(format!("failed to read {path}: {e}")))?;
+        content
+            .parse()
+            .map_err(|e| error(format!("failed to parse {path}: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf, process};
+
+    use super::*;
+
+    /// A config file in the temporary directory, deleted when dropped.
+    struct ConfigFile(PathBuf);
+
+    impl ConfigFile {
+        fn new(name: &str, content: &str) -> Self {
+            let path = env::temp_dir().join(format!("aoc-params-{}-{name}.toml", process::id()));
+            fs::write(&path, content).unwrap();
+            Self(path)
+        }
+
+        fn params(&self, args: &[&str]) -> Params {
+            let path = self.0.to_str().unwrap();
+            let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            args.extend(["--config".to_string(), path.to_string()]);
+            Params::from_args(&mut args)
+        }
+    }
+
+    impl Drop for ConfigFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn takes_its_options_out_of_the_arguments() {
+        let mut args: Vec<String> = ["day15", "1", "--param", "y = 10", "input.txt"]
+            .into_iter()
+            .chain(["--config", "example.toml", "--param", "cutoff=20"])
+            .map(String::from)
+            .collect();
+        let params = Params::from_args(&mut args);
+        assert_eq!(args, ["day15", "1", "input.txt"]);
+        assert_eq!(params.config_file.as_deref(), Some("example.toml"));
+        assert_eq!(params.lookup("y"), Ok(Some("10".to_string())));
+        assert_eq!(params.lookup("cutoff"), Ok(Some("20".to_string())));
+        assert_eq!(Params::default().lookup("y"), Ok(None));
+    }
+
+    #[test]
+    fn reads_numbers_strings_and_booleans_from_the_config_file() {
+        let config = ConfigFile::new(
+            "values",
+            "# The example's values.\ny = 10 # a row\nname = \"a # b\"\nscale = 0.5\nfloor = true\n",
+        );
+        let params = config.params(&["--param", "y=11"]);
+        assert_eq!(params.lookup("y"), Ok(Some("11".to_string())));
+        assert_eq!(params.lookup("name"), Ok(Some("a # b".to_string())));
+        assert_eq!(params.lookup("scale"), Ok(Some("0.5".to_string())));
+        assert_eq!(params.lookup("floor"), Ok(Some("true".to_string())));
+        assert_eq!(params.lookup("cutoff"), Ok(None));
+        assert_eq!(config.params(&[]).lookup("y"), Ok(Some("10".to_string())));
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_a_single_value() {
+        let config = ConfigFile::new("array", "y = [1, 2]\n");
+        let error = config.params(&[]).lookup("y").unwrap_err();
+        assert_eq!(error.name, "y");
+        assert!(error.message.ends_with("found [1, 2]"), "{}", error.message);
+    }
+
+    #[test]
+    fn rejects_a_config_file_that_is_not_toml() {
+        let config = ConfigFile::new("invalid", "y = 10\ncutoff\n");
+        let params = config.params(&[]);
+        let error = params.lookup("y").unwrap_err();
+        assert!(
+            error.message.starts_with("failed to parse "),
+            "{}",
+            error.message
+        );
+        assert!(error.message.contains("line 2"), "{}", error.message);
+        assert!(params.check(&["y", "cutoff"]).is_err());
+
+        let mut args = vec!["--config".to_string(), "missing.toml".to_string()];
+        let error = Params::from_args(&mut args).lookup("y").unwrap_err();
+        assert!(error.message.starts_with("failed to read missing.toml"));
+    }
+
+    #[test]
+    fn rejects_names_the_day_does_not_declare() {
+        let config = ConfigFile::new("names", "y = 10\ncutoff = 20\n");
+        assert_eq!(
+            config.params(&["--param", "y=1"]).check(&["y", "cutoff"]),
+            Ok(())
+        );
+
+        let error = config.params(&[]).check(&["y"]).unwrap_err();
+        assert_eq!(error.name, "cutoff");
+        assert_eq!(error.message, "unknown parameter cutoff: expected one of y");
+
+        let mut args = vec!["--param".to_string(), "rows=3".to_string()];
+        let error = Params::from_args(&mut args).check(&[]).unwrap_err();
+        assert_eq!(error.name, "rows");
+        assert_eq!(
+            error.message,
+            "unknown parameter rows: this day has no parameters"
+        );
+    }
+}
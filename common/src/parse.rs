@@ -0,0 +1,371 @@
+use std::{error::Error, fmt, process, str::FromStr};
+
+use crate::input;
+
+/// The text of a puzzle input, together with the name of the file it was read from.
+///
+/// Parsers are expected to work on subslices of [`Source::text`], so that any error
+/// can be traced back to the line and column of the offending fragment.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    name: &'a str,
+    text: &'a str,
+    first_line: usize,
+    ends_input: bool,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(name: &'a str, text: &'a str) -> Self {
+        Self {
+            name,
+            text,
+            first_line: 1,
+            ends_input: true,
+        }
+    }
+
+    /// A source for part of a file, whose text starts on the given 1-based line,
+    /// such as a single line read from a stream. Errors at the end of its text are
+    /// reported as being at the end of a line rather than the end of the input.
+    pub fn at_line(name: &'a str, text: &'a str, first_line: usize) -> Self {
+        Self {
+            name,
+            text,
+            first_line,
+            ends_input: false,
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The lines of the text, split the same way on every day. See [`input::lines`].
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        input::lines(self.text)
+    }
+
+    /// The paragraphs of the text, separated by blank lines. See [`input::paragraphs`].
+    pub fn paragraphs(&self) -> impl Iterator<Item = &'a str> {
+        input::paragraphs(self.text)
+    }
+
+    /// The byte offset of `fragment` within the source text.
+    ///
+    /// Fragments that don't point into the source are treated as being at the end of input.
+    pub fn offset_of(&self, fragment: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let position = fragment.as_ptr() as usize;
+        if position >= start && position <= start + self.text.len() {
+            position - start
+        } else {
+            self.text.len()
+        }
+    }
+
+    /// Build an error pointing at `fragment`, which must be a subslice of the source text.
+    pub fn error_at(&self, fragment: &str, expected: impl Into<String>) -> ParseError {
+        let offset = self.offset_of(fragment);
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |idx| offset + idx);
+        let snippet = &self.text[line_start..line_end];
+
+        let line = self.text[..line_start].matches('\n').count() + self.first_line;
+        let column = self.text[line_start..offset].chars().count() + 1;
+
+        // Report the fragment itself, or if it's empty, the next token in the input.
+        let rest_of_line = &self.text[offset..line_end];
+        let found = fragment
+            .split('\n')
+            .next()
+            .filter(|found| !found.is_empty() && rest_of_line.starts_with(found))
+            .unwrap_or_else(|| {
+                rest_of_line
+                    .split(char::is_whitespace)
+                    .next()
+                    .unwrap_or_default()
+            });
+
+        ParseError {
+            file: self.name.to_string(),
+            line,
+            column,
+            expected: expected.into(),
+            found: found.to_string(),
+            snippet: snippet.to_string(),
+            at_end: self.ends_input && offset == self.text.len(),
+        }
+    }
+
+    /// Parse `fragment` into a `T`, reporting `expected` at its location on failure.
+    pub fn parse_at<T: FromStr>(&self, fragment: &str, expected: &str) -> Result<T, ParseError> {
+        fragment
+            .parse()
+            .map_err(|_| self.error_at(fragment, expected))
+    }
+
+    pub fn strip_prefix(&self, fragment: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        fragment
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error_at(fragment, format!("`{prefix}`")))
+    }
+
+    pub fn split_once(
+        &self,
+        fragment: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        fragment.split_once(delimiter).ok_or_else(|| {
+            let end = &fragment[fragment.len()..];
+            self.error_at(end, format!("`{delimiter}`"))
+        })
+    }
+
+    /// Check that `fragment` is exactly one of the given options.
+    pub fn one_of(&self, fragment: &'a str, options: &[&str]) -> Result<&'a str, ParseError> {
+        if options.contains(&fragment) {
+            Ok(fragment)
+        } else {
+            let expected = match options {
+                [only] => format!("`{only}`"),
+                [init @ .., last] => format!(
+                    "one of {} or `{last}`",
+                    init.iter()
+                        .map(|option| format!("`{option}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                [] => "nothing".to_string(),
+            };
+            Err(self.error_at(fragment, expected))
+        }
+    }
+
+    /// Report a failed `nom` parse, using nom's own description of what went wrong.
+    #[cfg(feature = "nom")]
+    pub fn nom_error(&self, error: nom::Err<nom::error::Error<&str>>) -> ParseError {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                self.error_at(next_token(e.input), e.code.description().to_lowercase())
+            }
+            nom::Err::Incomplete(_) => self.error_at(&self.text[self.text.len()..], "more input"),
+        }
+    }
+
+    /// Report a failed `nom` parse whose errors were collected into a `nom_supreme` error tree.
+    ///
+    /// When several alternatives failed, the ones that made it furthest into the input
+    /// are reported together, since they are the most likely to be what was meant.
+    #[cfg(feature = "nom")]
+    pub fn error_tree(&self, error: nom::Err<nom_supreme::error::ErrorTree<&str>>) -> ParseError {
+        match error {
+            nom::Err::Error(tree) | nom::Err::Failure(tree) => {
+                let mut expectations = vec![];
+                self.collect_expectations(tree, &mut expectations);
+
+                let furthest = expectations
+                    .iter()
+                    .map(|(offset, _, _)| *offset)
+                    .max()
+                    .expect("error trees always contain at least one base error");
+                let mut location = None;
+                let mut expected: Vec<String> = vec![];
+                for (offset, fragment, expectation) in expectations {
+                    if offset == furthest && !expected.contains(&expectation) {
+                        location.get_or_insert(fragment);
+                        expected.push(expectation);
+                    }
+                }
+
+                self.error_at(next_token(location.unwrap()), expected.join(" or "))
+            }
+            nom::Err::Incomplete(_) => self.error_at(&self.text[self.text.len()..], "more input"),
+        }
+    }
+
+    #[cfg(feature = "nom")]
+    fn collect_expectations(
+        &self,
+        tree: nom_supreme::error::ErrorTree<&'a str>,
+        expectations: &mut Vec<(usize, &'a str, String)>,
+    ) {
+        use nom_supreme::error::{BaseErrorKind, Expectation, GenericErrorTree};
+
+        match tree {
+            GenericErrorTree::Base { location, kind } => {
+                let expectation = match kind {
+                    BaseErrorKind::Expected(Expectation::Eof) => "end of input".to_string(),
+                    BaseErrorKind::Expected(Expectation::Something) => "more input".to_string(),
+                    BaseErrorKind::Expected(expectation) => expectation.to_string(),
+                    BaseErrorKind::Kind(kind) => kind.description().to_lowercase(),
+                    BaseErrorKind::External(e) => e.to_string(),
+                };
+                expectations.push((self.offset_of(location), location, expectation));
+            }
+            GenericErrorTree::Stack { base, .. } => self.collect_expectations(*base, expectations),
+            GenericErrorTree::Alt(siblings) => {
+                for sibling in siblings {
+                    self.collect_expectations(sibling, expectations);
+                }
+            }
+        }
+    }
+}
+
+/// The token at the start of a parser's remaining input: a run of alphanumeric characters,
+/// or otherwise a single character.
+#[cfg(feature = "nom")]
+fn next_token(input: &str) -> &str {
+    let end = match input.char_indices().next() {
+        Some((_, c)) if c.is_alphanumeric() => input
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(input.len()),
+        Some((_, c)) => c.len_utf8(),
+        None => 0,
+    };
+    &input[..end]
+}
+
+/// A malformed puzzle input, with enough context to show the user where the problem is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+
+    /// 1-based line number of the error.
+    pub line: usize,
+
+    /// 1-based column number of the error, counted in characters.
+    pub column: usize,
+
+    /// Description of the token that would have been valid at this location.
+    pub expected: String,
+
+    /// The token actually present at this location. Empty at the end of a line or of the input.
+    pub found: String,
+
+    /// The full text of the line on which the error occurred.
+    pub snippet: String,
+
+    at_end: bool,
+}
+
+impl ParseError {
+    /// Print the error to stderr and exit the process with a failure status.
+    pub fn exit(&self) -> ! {
+        eprintln!("{self}");
+        process::exit(1)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = if !self.found.is_empty() {
+            format!("`{}`", self.found)
+        } else if self.at_end {
+            "end of input".to_string()
+        } else {
+            "end of line".to_string()
+        };
+        writeln!(f, "error: expected {}, found {found}", self.expected)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        let padding = " ".repeat(self.column - 1);
+        let underline = "^".repeat(self.found.chars().count().max(1));
+        writeln!(f, "{gutter}--> {}:{}:{}", self.file, self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{gutter} | {padding}{underline}")
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_the_fragment_by_line_and_character_column() {
+        let text = "first line\nnaïve token here\n";
+        let source = Source::new("input.txt", text);
+        let fragment = &text[text.find("token").unwrap()..][..5];
+        let error = source.error_at(fragment, "a number");
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.found, "token");
+        assert_eq!(error.snippet, "naïve token here");
+        assert_eq!(source.offset_of(fragment), 18);
+
+        let line = Source::at_line("input.txt", "a b", 42);
+        let error = line.error_at(&line.text()[2..], "a number");
+        assert_eq!((error.line, error.column), (42, 3));
+    }
+
+    #[test]
+    fn finds_the_next_token_after_an_empty_fragment() {
+        let text = "move 3 from";
+        let source = Source::new("input.txt", text);
+        let error = source.error_at(&text[7..7], "`to`");
+        assert_eq!(error.found, "from");
+
+        let error = source.split_once(&text[..6], " to ").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (7, ""));
+    }
+
+    #[test]
+    fn renders_the_found_token_or_where_the_input_ended() {
+        let source = Source::new("input.txt", "12 x4\n");
+        let error = source.parse_at::<u32>(&source.text()[3..5], "a number");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "error: expected a number, found `x4`\n \
+             --> input.txt:1:4\n  |\n1 | 12 x4\n  |    ^^"
+        );
+
+        let end = &source.text()[source.text().len()..];
+        let error = source.error_at(end, "a number");
+        assert!(error
+            .to_string()
+            .starts_with("error: expected a number, found end of input\n"));
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let line = Source::at_line("input.txt", "12", 3);
+        let error = line.error_at(&line.text()[2..], "a comma");
+        assert!(error
+            .to_string()
+            .starts_with("error: expected a comma, found end of line\n"));
+        assert!(error.to_string().ends_with("3 | 12\n  |   ^"), "{error}");
+    }
+
+    #[test]
+    fn one_of_lists_the_options() {
+        let source = Source::new("input.txt", "up");
+        let error = source.one_of(source.text(), &["L", "R", "U"]).unwrap_err();
+        assert_eq!(error.expected, "one of `L`, `R` or `U`");
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn error_tree_reports_the_alternatives_that_got_furthest() {
+        use nom::{branch::alt, sequence::pair, Parser};
+        use nom_supreme::{error::ErrorTree, tag::complete::tag};
+
+        let text = "ad";
+        let source = Source::new("input.txt", text);
+        let mut parser = alt((
+            pair(tag("a"), tag("b")),
+            pair(tag("a"), tag("c")),
+            pair(tag("x"), tag("y")),
+        ));
+        let result: nom::IResult<&str, (&str, &str), ErrorTree<&str>> = parser.parse(text);
+        let error = source.error_tree(result.unwrap_err());
+        assert_eq!(error.column, 2);
+        assert_eq!(error.expected, r#""b" or "c""#);
+        assert_eq!(error.found, "d");
+    }
+}
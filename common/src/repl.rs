@@ -0,0 +1,151 @@
+//! An interactive prompt for exploring a parsed puzzle input, for debugging without adding
+//! `println!`s to the solution. Each day that has one registers its own commands, which
+//! get the parsed input and whatever else the day keeps between commands as their state.
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+/// The part argument that starts the REPL instead of solving a part.
+pub const PART: &str = "repl";
+
+type Run<'a, S> = Box<dyn FnMut(&mut S, &[&str]) -> Result<String, String> + 'a>;
+
+struct Command<'a, S> {
+    name: &'static str,
+    usage: &'static str,
+    help: &'static str,
+    run: Run<'a, S>,
+}
+
+/// A day's commands, and the state they share.
+pub struct Repl<'a, S> {
+    day: u32,
+    state: S,
+    commands: Vec<Command<'a, S>>,
+}
+
+impl<'a, S> Repl<'a, S> {
+    pub fn new(day: u32, state: S) -> Self {
+        Self {
+            day,
+            state,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Add a command, which is given the state and the words after its name, and returns
+    /// what to print or an error message. The usage lists its arguments, like `<x> <y>`.
+    pub fn command(
+        mut self,
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        run: impl FnMut(&mut S, &[&str]) -> Result<String, String> + 'a,
+    ) -> Self {
+        self.commands.push(Command {
+            name,
+            usage,
+            help,
+            run: Box::new(run),
+        });
+        self
+    }
+
+    /// Read commands from stdin until `quit` or the end of the input.
+    pub fn run(self) -> io::Result<()> {
+        self.run_with(io::stdin().lock(), io::stdout().lock())
+    }
+
+    pub fn run_with(mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(
+            output,
+            "day {}: type `help` for a list of commands",
+            self.day
+        )?;
+        let mut line = String::new();
+        loop {
+            write!(output, "day{}> ", self.day)?;
+            output.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(output)?;
+                return Ok(());
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some((name, args)) = words.split_first() else {
+                continue;
+            };
+            match *name {
+                "quit" | "exit" => return Ok(()),
+                "help" => self.help(&mut output)?,
+                _ => match self.commands.iter_mut().find(|c| c.name == *name) {
+                    Some(command) => match (command.run)(&mut self.state, args) {
+                        Ok(result) => writeln!(output, "{}", result.trim_end())?,
+                        Err(message) => writeln!(output, "error: {message}")?,
+                    },
+                    None => writeln!(output, "error: unknown command `{name}`, try `help`")?,
+                },
+            }
+        }
+    }
+
+    fn help(&self, output: &mut impl Write) -> io::Result<()> {
+        let builtins = [("help", "", "list the commands"), ("quit", "", "leave")];
+        let commands = self
+            .commands
+            .iter()
+            .map(|c| (c.name, c.usage, c.help))
+            .chain(builtins);
+        for (name, usage, help) in commands {
+            let command = format!("{name} {usage}");
+            writeln!(output, "  {command:<24}{help}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the argument at `index`, or say what was expected there if it's missing or invalid.
+pub fn arg<T>(args: &[&str], index: usize, expected: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let arg = args
+        .get(index)
+        .ok_or_else(|| format!("expected {expected}"))?;
+    arg.parse()
+        .map_err(|e| format!("expected {expected}, found `{arg}`: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_commands_until_quit() {
+        let repl = Repl::new(1, 0).command("add", "<n>", "add to the total", |total, args| {
+            *total += arg::<i64>(args, 0, "a number")?;
+            Ok(total.to_string())
+        });
+        let input = "add 2\n\nadd 3\nadd x\nsub 1\nquit\nadd 4\n";
+        let mut output = Vec::new();
+        repl.run_with(input.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let responses: Vec<&str> = output.lines().skip(1).collect();
+        assert_eq!(
+            responses,
+            [
+                "day1> 2",
+                "day1> day1> 5",
+                "day1> error: expected a number, found `x`: invalid digit found in string",
+                "day1> error: unknown command `sub`, try `help`",
+                "day1> ",
+            ]
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    any::Any,
+    fmt::Display,
+    fs,
+    io::{self, BufRead, Read},
+    panic::{self, AssertUnwindSafe},
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+use tracing::{info, info_span};
+
+use crate::alloc::{AllocStats, Measurement};
+use crate::answer::Answer;
+use crate::input;
+use crate::num::ArithmeticError;
+use crate::params::Params;
+use crate::parse::ParseError;
+use crate::stream::{LineReader, StreamError};
+
+/// How a solution reports its answer, or the reason it doesn't have one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Just the answer on stdout, and errors on stderr.
+    Text,
+
+    /// A single JSON object on stdout, with the answer or error and how long each step took.
+    Json,
+}
+
+impl Format {
+    /// Take the `--format <text|json>` option out of the command line arguments,
+    /// leaving the positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let Some(idx) = args.iter().position(|arg| arg == "--format") else {
+            return Format::Text;
+        };
+        args.remove(idx);
+        let format = args.get(idx).cloned().expect("output format");
+        args.remove(idx);
+        match format.as_str() {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            _ => unreachable!("{}", format),
+        }
+    }
+}
+
+/// Runs the steps of a solution, timing them and reporting the outcome in the requested format.
+pub struct Report {
+    day: u32,
+    part: u32,
+    format: Format,
+    parse_time: Duration,
+    parse_memory: Option<AllocStats>,
+}
+
+impl Report {
+    pub fn new(day: u32, part: &str, format: Format) -> Self {
+        Self {
+            day,
+            part: part.parse().expect("part number"),
+            format,
+            parse_time: Duration::ZERO,
+            parse_memory: None,
+        }
+    }
+
+    /// A report for exploring the input in the REPL, which only reads and parses it, and
+    /// reports any errors doing so as text.
+    pub fn repl(day: u32) -> Self {
+        Self {
+            day,
+            part: 0,
+            format: Format::Text,
+            parse_time: Duration::ZERO,
+            parse_memory: None,
+        }
+    }
+
+    /// Read the whole input file, or stdin if the path is `-`, and normalise it with
+    /// [`input::normalize`].
+    pub fn read_input(&self, path: &str) -> String {
+        let content = if path == "-" {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content).map(|_| content)
+        } else {
+            fs::read_to_string(path)
+        };
+        let content = content.unwrap_or_else(|e| self.fail_to_read(path, e));
+        input::normalize(&content)
+    }
+
+    /// Open the input file, or stdin if the path is `-`, to be read a line at a time.
+    pub fn open_stream(&self, path: &str) -> LineReader<Box<dyn BufRead>> {
+        LineReader::open(path).unwrap_or_else(|e| self.fail_to_read(path, e))
+    }
+
+    fn fail_to_read(&self, path: &str, e: io::Error) -> ! {
+        self.fail(
+            format!("error: failed to read {path}: {e}"),
+            json!({ "kind": io_error_kind(&e), "message": format!("failed to read {path}: {e}") }),
+            1,
+        )
+    }
+
+    /// Exit with a report that the day doesn't support an option it was run with, such as
+    /// `--stream` for a part that can only be solved from the whole input.
+    pub fn unsupported(&self, option: &str) -> ! {
+        let message = format!(
+            "{option} isn't supported for day {} part {}",
+            self.day, self.part
+        );
+        self.fail(
+            format!("error: {message}"),
+            json!({ "kind": "usage", "message": message, "option": option }),
+            1,
+        )
+    }
+
+    /// Exit with a report of the first parameter given that isn't one of the day's.
+    pub fn check_params(&self, params: &Params, declared: &[&str]) {
+        params
+            .check(declared)
+            .unwrap_or_else(|e| self.fail_param(e.message, &e.name));
+    }
+
+    /// Look up a puzzle parameter, falling back to its value for the real puzzle input,
+    /// and exiting with a report of the error if the value given isn't valid.
+    pub fn param<T>(&self, params: &Params, name: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = params
+            .lookup(name)
+            .unwrap_or_else(|e| self.fail_param(e.message, &e.name));
+        let Some(value) = value else {
+            return default;
+        };
+        value.parse().unwrap_or_else(|e| {
+            self.fail_param(
+                format!("invalid value {value:?} for parameter {name}: {e}"),
+                name,
+            )
+        })
+    }
+
+    fn fail_param(&self, message: String, name: &str) -> ! {
+        self.fail(
+            format!("error: {message}"),
+            json!({ "kind": "param", "message": message, "name": name }),
+            1,
+        )
+    }
+
+    /// Run the input parser, exiting with a report of the error if the input is malformed.
+    pub fn parse<T>(&mut self, parser: impl FnOnce() -> Result<T, ParseError>) -> T {
+        let _span = info_span!("parse", day = self.day).entered();
+        let measurement = Measurement::start();
+        let start = Instant::now();
+        let parsed = self.catch_panic(parser);
+        self.parse_time = start.elapsed();
+        self.parse_memory = measurement.finish();
+        log_memory(self.parse_memory);
+
+        parsed.unwrap_or_else(|e| self.fail_to_parse(e))
+    }
+
+    fn fail_to_parse(&self, e: ParseError) -> ! {
+        self.fail(e.to_string(), parse_error(&e), 1)
+    }
+
+    /// Run the solver and report its answer.
+    pub fn solve<T: Display>(&self, solver: impl FnOnce() -> T) {
+        let (answer, solve_time, solve_memory) = self.run_solver(solver);
+        self.report_answer(answer, solve_time, solve_memory);
+    }
+
+    /// Run a solver whose arithmetic is checked, and report its answer, or the calculation
+    /// that overflowed.
+    pub fn solve_checked<T: Display>(&self, solver: impl FnOnce() -> Result<T, ArithmeticError>) {
+        match self.run_solver(solver) {
+            (Ok(answer), solve_time, solve_memory) => {
+                self.report_answer(answer, solve_time, solve_memory)
+            }
+            (Err(e), _, _) => self.fail_arithmetic(e),
+        }
+    }
+
+    /// Run a solver that parses its input as it reads it, and report its answer. Parsing
+    /// isn't timed separately, since it's interleaved with solving.
+    pub fn solve_stream<T: Display>(&self, solver: impl FnOnce() -> Result<T, StreamError>) {
+        let (answer, solve_time, solve_memory) = self.run_solver(solver);
+        match answer {
+            Ok(answer) => self.report_answer(answer, solve_time, solve_memory),
+            Err(StreamError::Io(e)) => self.fail(
+                format!("error: failed to read input: {e}"),
+                json!({ "kind": io_error_kind(&e), "message": format!("failed to read input: {e}") }),
+                1,
+            ),
+            Err(StreamError::Parse(e)) => self.fail_to_parse(e),
+            Err(StreamError::Arithmetic(e)) => self.fail_arithmetic(e),
+        }
+    }
+
+    /// Run a solver in its span, timing it and measuring its allocations.
+    fn run_solver<T>(&self, solver: impl FnOnce() -> T) -> (T, Duration, Option<AllocStats>) {
+        let span = info_span!("part", day = self.day, part = self.part).entered();
+        let measurement = Measurement::start();
+        let start = Instant::now();
+        let answer = self.catch_panic(solver);
+        let solve_time = start.elapsed();
+        let solve_memory = measurement.finish();
+        log_memory(solve_memory);
+        span.exit();
+
+        (answer, solve_time, solve_memory)
+    }
+
+    fn fail_arithmetic(&self, e: ArithmeticError) -> ! {
+        self.fail(format!("error: {e}"), arithmetic_error(&e), 1)
+    }
+
+    fn report_answer<T: Display>(
+        &self,
+        answer: T,
+        solve_time: Duration,
+        solve_memory: Option<AllocStats>,
+    ) {
+        let answer = Answer::from_display(answer);
+        match self.format {
+            Format::Text => println!("{answer}"),
+            Format::Json => println!("{}", self.answer_json(&answer, solve_time, solve_memory)),
+        }
+    }
+
+    /// The JSON report of an answer, with how long each step took and, when allocations
+    /// are counted, how much each step allocated.
+    fn answer_json(
+        &self,
+        answer: &Answer,
+        solve_time: Duration,
+        solve_memory: Option<AllocStats>,
+    ) -> Value {
+        let mut report = json!({
+            "day": self.day,
+            "part": self.part,
+            "status": "ok",
+            "answer": answer,
+            "parse_time_us": self.parse_time.as_micros() as u64,
+            "solve_time_us": solve_time.as_micros() as u64,
+        });
+        if let Some(solve_memory) = solve_memory {
+            let mut memory = json!({});
+            if let Some(parse_memory) = self.parse_memory {
+                memory["parse"] = parse_memory.to_json();
+            }
+            memory["solve"] = solve_memory.to_json();
+            report["memory"] = memory;
+        }
+        report
+    }
+
+    /// Panics are left alone in text mode. In JSON mode they are reported like any other error,
+    /// after the panic hook has printed the usual message to stderr.
+    fn catch_panic<T>(&self, f: impl FnOnce() -> T) -> T {
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(value) => value,
+            Err(payload) if self.format == Format::Json => {
+                let message = panic_message(payload.as_ref());
+                self.fail(
+                    message.clone(),
+                    json!({ "kind": "panic", "message": message }),
+                    101,
+                )
+            }
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    fn fail(&self, text: String, error: Value, exit_code: i32) -> ! {
+        match self.format {
+            Format::Text => eprintln!("{text}"),
+            Format::Json => println!("{}", self.error_json(error)),
+        }
+        process::exit(exit_code)
+    }
+
+    /// The JSON report of an error, which is an object with at least a `kind` and a `message`.
+    fn error_json(&self, error: Value) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "status": "error",
+            "error": error,
+        })
+    }
+}
+
+fn parse_error(e: &ParseError) -> Value {
+    json!({
+        "kind": "parse",
+        "message": format!("expected {}", e.expected),
+        "file": e.file,
+        "line": e.line,
+        "column": e.column,
+        "expected": e.expected,
+        "found": e.found,
+    })
+}
+
+fn arithmetic_error(e: &ArithmeticError) -> Value {
+    let (kind, expression) = match e {
+        ArithmeticError::Overflow { expression, .. } => ("overflow", expression),
+        ArithmeticError::DivisionByZero { expression } => ("division_by_zero", expression),
+    };
+    json!({ "kind": kind, "message": e.to_string(), "expression": expression })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// The error kind to report for a failed read. Running out of memory while reading the input
+/// gets a kind of its own, so that runners can tell it apart from a missing file.
+fn io_error_kind(e: &io::Error) -> &'static str {
+    match e.kind() {
+        io::ErrorKind::OutOfMemory => "oom",
+        _ => "io",
+    }
+}
+
+/// Log the allocations made during a step, inside the step's span, if they're being counted.
+fn log_memory(stats: Option<AllocStats>) {
+    if let Some(stats) = stats {
+        info!(
+            allocations = stats.allocations,
+            allocated_bytes = stats.allocated_bytes,
+            peak_heap_bytes = stats.peak_heap_bytes,
+            "memory"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{arithmetic_error, parse_error, Format, Report};
+    use crate::alloc::AllocStats;
+    use crate::answer::Answer;
+    use crate::num::ArithmeticError;
+    use crate::parse::Source;
+
+    fn report() -> Report {
+        let mut report = Report::new(1, "2", Format::Json);
+        report.parse_time = Duration::from_micros(250);
+        report
+    }
+
+    #[test]
+    fn reports_an_answer_with_its_timings() {
+        let answer = Answer::from_display(24000);
+        let report = report().answer_json(&answer, Duration::from_micros(1500), None);
+        assert_eq!(
+            report,
+            json!({
+                "day": 1,
+                "part": 2,
+                "status": "ok",
+                "answer": 24000,
+                "parse_time_us": 250,
+                "solve_time_us": 1500,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_the_allocations_of_each_step() {
+        let stats = |allocations| AllocStats {
+            allocations,
+            allocated_bytes: allocations * 16,
+            peak_heap_bytes: 1024,
+        };
+        let mut report = report();
+        report.parse_memory = Some(stats(3));
+        let answer = Answer::from_display("FCVRLMVQP");
+        let report = report.answer_json(&answer, Duration::from_micros(1500), Some(stats(5)));
+        assert_eq!(report["answer"], "FCVRLMVQP");
+        assert_eq!(
+            report["memory"],
+            json!({
+                "parse": { "allocations": 3, "allocated_bytes": 48, "peak_heap_bytes": 1024 },
+                "solve": { "allocations": 5, "allocated_bytes": 80, "peak_heap_bytes": 1024 },
+            })
+        );
+    }
+
+    #[test]
+    fn reports_where_the_input_is_malformed() {
+        let source = Source::new("input.txt", "1\nx2\n");
+        let line = source.lines().nth(1).unwrap();
+        let e = source.parse_at::<u32>(line, "a number").unwrap_err();
+        assert_eq!(
+            report().error_json(parse_error(&e)),
+            json!({
+                "day": 1,
+                "part": 2,
+                "status": "error",
+                "error": {
+                    "kind": "parse",
+                    "message": "expected a number",
+                    "file": "input.txt",
+                    "line": 2,
+                    "column": 1,
+                    "expected": "a number",
+                    "found": "x2",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn reports_the_expression_that_overflowed() {
+        let e = ArithmeticError::Overflow {
+            expression: "99 * 99".to_string(),
+            int_type: "i8",
+        };
+        assert_eq!(
+            report().error_json(arithmetic_error(&e))["error"],
+            json!({
+                "kind": "overflow",
+                "message": "99 * 99 overflows i8",
+                "expression": "99 * 99",
+            })
+        );
+
+        let e = ArithmeticError::DivisionByZero {
+            expression: "1 / 0".to_string(),
+        };
+        assert_eq!(
+            report().error_json(arithmetic_error(&e))["error"]["kind"],
+            "division_by_zero"
+        );
+    }
+}
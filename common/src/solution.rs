@@ -0,0 +1,269 @@
+//! The command line of a day whose parser and solutions are declared with the `#[aoc]` and
+//! `#[aoc_parser]` attributes, from the `macros` feature. The attributes register each part
+//! as a [`Solution`], and [`aoc_main!`](crate::aoc_main) turns a day's solutions into its
+//! `main`.
+
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use crate::answer::Answer;
+#[cfg(feature = "image")]
+use crate::image::PngExport;
+use crate::logging;
+use crate::num::IntType;
+use crate::params::Params;
+use crate::repl;
+use crate::report::{Format, Report};
+use crate::stream;
+#[cfg(feature = "visualize")]
+use crate::visualize::Visualize;
+
+/// The options a part is run with, taken out of the command line arguments.
+pub struct Args {
+    pub params: Params,
+    pub streaming: bool,
+    pub int_type: IntType,
+    #[cfg(feature = "visualize")]
+    pub visualize: Visualize,
+    #[cfg(feature = "image")]
+    pub png: Option<PngExport>,
+}
+
+impl Args {
+    /// Take the options out of the command line arguments, leaving the positional arguments
+    /// in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        Self {
+            params: Params::from_args(args),
+            streaming: stream::from_args(args),
+            int_type: IntType::from_args(args),
+            // This takes `--png` for a picture of the final frame, so it goes first.
+            #[cfg(feature = "visualize")]
+            visualize: Visualize::from_args(args),
+            #[cfg(feature = "image")]
+            png: PngExport::from_args(args),
+        }
+    }
+}
+
+/// Everything `main` can do with a day, as collected by [`aoc_main!`](crate::aoc_main).
+pub struct Day {
+    pub day: u32,
+    pub parts: [Solution; 2],
+
+    /// Parse the input, given its file name, and explore it in the REPL.
+    pub repl: Option<fn(&mut Report, &str)>,
+
+    /// Parse the input again after solving, and draw it to the `--png` file.
+    pub png: Option<fn(&mut Report, &Args, &str)>,
+}
+
+/// A part of a day, as generated by `#[aoc]`.
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+
+    /// The names of the puzzle parameters the part takes.
+    pub params: &'static [&'static str],
+
+    /// Read and parse the input, given its file name, or stream it with `--stream`, and solve
+    /// the part with the options from the command line, reporting the outcome.
+    pub run: fn(&mut Report, &Args, &str),
+
+    /// Normalise and parse the input, and solve the part with the default parameters,
+    /// returning the answer or the error message, without any timing or reporting. This is
+    /// the hook for benchmarks, which call it over and over.
+    pub answer: fn(&str) -> Result<Answer, String>,
+}
+
+/// The timing of repeated runs of a solution.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub answer: Result<Answer, String>,
+    pub times: Vec<Duration>,
+}
+
+impl Bench {
+    pub fn min(&self) -> Duration {
+        self.times.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut times = self.times.clone();
+        times.sort_unstable();
+        times.get(times.len() / 2).copied().unwrap_or_default()
+    }
+}
+
+/// Parse and solve the input `runs` times, stopping early if it fails.
+pub fn bench(solution: &Solution, content: &str, runs: usize) -> Bench {
+    let mut times = Vec::with_capacity(runs);
+    let mut answer = Err("no runs".to_string());
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = (solution.answer)(content);
+        times.push(start.elapsed());
+        if answer.is_err() {
+            break;
+        }
+    }
+    Bench { answer, times }
+}
+
+/// Take the `--bench <runs>` option out of the command line arguments, leaving the
+/// positional arguments in place.
+fn bench_runs_from_args(args: &mut Vec<String>) -> Option<usize> {
+    let idx = args.iter().position(|arg| arg == "--bench")?;
+    args.remove(idx);
+    let runs = args.get(idx).cloned().expect("number of runs");
+    args.remove(idx);
+    Some(runs.parse().expect("number of runs"))
+}
+
+/// Run the part given on the command line, or the REPL for the part `repl`. With
+/// `--bench <runs>`, the part is solved that many times with the default parameters instead,
+/// and the answer is printed with the fastest and median times.
+pub fn main(day: &Day) {
+    let mut args: Vec<String> = env::args().collect();
+    let format = Format::from_args(&mut args);
+    let options = Args::from_args(&mut args);
+    let bench_runs = bench_runs_from_args(&mut args);
+    logging::init_from_args(&mut args);
+    let mut reversed_args: Vec<_> = args.iter().map(|x| x.as_str()).rev().collect();
+
+    reversed_args
+        .pop()
+        .expect("Expected the executable name to be the first argument, but was missing");
+
+    let part = reversed_args.pop().expect("part number");
+    let input_file = reversed_args.pop().expect("input file");
+
+    if part == repl::PART {
+        let Some(run_repl) = day.repl else {
+            unreachable!("{}", part);
+        };
+        return run_repl(&mut Report::repl(day.day), input_file);
+    }
+    let Some(solution) = day.parts.iter().find(|s| s.part.to_string() == part) else {
+        unreachable!("{}", part);
+    };
+    let mut report = Report::new(day.day, part, format);
+    // A config file can have the parameters for both parts.
+    let declared: Vec<_> = day.parts.iter().flat_map(|s| s.params).copied().collect();
+    report.check_params(&options.params, &declared);
+
+    match bench_runs {
+        Some(runs) => {
+            let content = report.read_input(input_file);
+            let bench = bench(solution, &content, runs);
+            match &bench.answer {
+                Ok(answer) => println!("{answer}"),
+                Err(e) => eprintln!("error: {e}"),
+            }
+            eprintln!(
+                "min {:?}, median {:?} over {} runs",
+                bench.min(),
+                bench.median(),
+                bench.times.len()
+            );
+        }
+        None => {
+            (solution.run)(&mut report, &options, input_file);
+            if let Some(draw) = day.png.filter(|_| !options.streaming) {
+                draw(&mut report, &options, input_file);
+            }
+        }
+    }
+}
+
+/// Define a day's `main`, running the parts registered with `#[aoc]` in the day's library,
+/// and optionally its REPL, which takes the parsed input by reference, and a picture of the
+/// input for `--png`, drawn by a function that takes it with the `PngExport`.
+///
+/// ```ignore
+/// common::aoc_main!(day15, repl = repl);
+/// common::aoc_main!(day8, png = draw_scenic_scores);
+/// ```
+#[macro_export]
+macro_rules! aoc_main {
+    ($day:ident $(, repl = $repl:ident)? $(, png = $png:ident)?) => {
+        fn main() {
+            $crate::solution::main(&$crate::solution::Day {
+                day: $day::__AOC_DAY,
+                parts: [$day::__AOC_PART1, $day::__AOC_PART2],
+                repl: $crate::__aoc_hook!($(
+                    |report: &mut $crate::report::Report, file: &str| {
+                        let content = report.read_input(file);
+                        let input = report.parse(|| {
+                            $day::__aoc_parse($crate::parse::Source::new(file, &content))
+                        });
+                        $day::$repl(&input).run().expect("failed to run the REPL");
+                    }
+                )?),
+                png: $crate::__aoc_hook!($(
+                    |report: &mut $crate::report::Report,
+                     args: &$crate::solution::Args,
+                     file: &str| {
+                        let Some(png) = &args.png else {
+                            return;
+                        };
+                        let content = report.read_input(file);
+                        let input = report.parse(|| {
+                            $day::__aoc_parse($crate::parse::Source::new(file, &content))
+                        });
+                        $day::$png(&input, png).expect("failed to write the image");
+                    }
+                )?),
+            });
+        }
+    };
+}
+
+/// `Some` of the hook given to [`aoc_main!`](crate::aoc_main), or `None` without one.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aoc_hook {
+    () => {
+        None
+    };
+    ($hook:expr) => {
+        Some($hook)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_lines(content: &str) -> Result<Answer, String> {
+        match content.lines().count() {
+            0 => Err("empty input".to_string()),
+            lines => Ok(Answer::from_display(lines)),
+        }
+    }
+
+    const COUNT_LINES: Solution = Solution {
+        day: 1,
+        part: 1,
+        params: &[],
+        run: |_, _, _| unreachable!(),
+        answer: count_lines,
+    };
+
+    #[test]
+    fn benches_every_run() {
+        let bench = bench(&COUNT_LINES, "a\nb\n", 3);
+        assert_eq!(bench.answer, Ok(Answer::Integer(2)));
+        assert_eq!(bench.times.len(), 3);
+        assert!(bench.min() <= bench.median());
+    }
+
+    #[test]
+    fn stops_benching_after_an_error() {
+        let bench = bench(&COUNT_LINES, "", 3);
+        assert_eq!(bench.answer, Err("empty input".to_string()));
+        assert_eq!(bench.times.len(), 1);
+    }
+}
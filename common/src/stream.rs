@@ -0,0 +1,282 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    iter,
+};
+
+use crate::input::BOM;
+use crate::num::ArithmeticError;
+use crate::parse::{ParseError, Source};
+
+/// Take the `--stream` flag out of the command line arguments, returning whether it was given.
+///
+/// Days that support it then read their input a line at a time with a [`LineReader`],
+/// instead of reading the whole file into memory first.
+pub fn from_args(args: &mut Vec<String>) -> bool {
+    let Some(idx) = args.iter().position(|arg| arg == "--stream") else {
+        return false;
+    };
+    args.remove(idx);
+    true
+}
+
+/// Why a streamed input couldn't be solved: either reading it failed partway through,
+/// one of its lines was malformed, or a calculation with it overflowed.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+    Arithmetic(ArithmeticError),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Io(error)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(error: ParseError) -> Self {
+        StreamError::Parse(error)
+    }
+}
+
+impl From<ArithmeticError> for StreamError {
+    fn from(error: ArithmeticError) -> Self {
+        StreamError::Arithmetic(error)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "error: failed to read input: {e}"),
+            StreamError::Parse(e) => write!(f, "{e}"),
+            StreamError::Arithmetic(e) => write!(f, "error: {e}"),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+/// A puzzle input read one line at a time, reusing the same buffer for every line, so that
+/// inputs far larger than memory can be solved.
+///
+/// Each line is handed out as a [`Source`] of its own, which reports errors at the line's
+/// position in the whole input. Lines are normalised the same way as a whole input read with
+/// [`crate::input::normalize`]: a byte order mark, line endings and trailing whitespace are
+/// stripped, and blank lines at the end of the input are skipped. Only `\n` and `\r\n` end a
+/// line, though, not a lone `\r`.
+pub struct LineReader<R> {
+    name: String,
+    reader: R,
+    line: String,
+    line_number: usize,
+
+    /// Blank lines that have been read but not handed out yet, because they might turn out
+    /// to be at the end of the input.
+    pending_blank_lines: usize,
+    held_line: bool,
+}
+
+impl LineReader<Box<dyn BufRead>> {
+    /// Open a file for streaming, or stdin if the path is `-`.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let reader: Box<dyn BufRead> = if path == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+        Ok(Self::new(path, reader))
+    }
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(name: impl Into<String>, reader: R) -> Self {
+        Self {
+            name: name.into(),
+            reader,
+            line: String::new(),
+            line_number: 0,
+            pending_blank_lines: 0,
+            held_line: false,
+        }
+    }
+
+    /// The next line of the input, without its line ending, or `None` at the end of the input.
+    pub fn next_line(&mut self) -> io::Result<Option<Source<'_>>> {
+        if self.pending_blank_lines == 0 && !self.held_line {
+            loop {
+                if !self.read_line()? {
+                    return Ok(None);
+                }
+                if !self.line.trim_end().is_empty() {
+                    self.held_line = true;
+                    break;
+                }
+                self.pending_blank_lines += 1;
+            }
+        }
+
+        if self.pending_blank_lines > 0 {
+            let line = self.line_number - self.pending_blank_lines;
+            self.pending_blank_lines -= 1;
+            return Ok(Some(Source::at_line(&self.name, "", line)));
+        }
+
+        self.held_line = false;
+        Ok(Some(Source::at_line(
+            &self.name,
+            self.line.trim_end(),
+            self.line_number,
+        )))
+    }
+
+    /// Parse each remaining line of the input in turn, for solvers that work on an iterator of
+    /// parsed values. Iterating stops after the first error.
+    pub fn parse_lines<'r, T>(
+        &'r mut self,
+        mut parse: impl FnMut(Source<'_>) -> Result<T, ParseError> + 'r,
+    ) -> impl Iterator<Item = Result<T, StreamError>> + 'r {
+        let mut failed = false;
+        iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let parsed = match self.next_line() {
+                Ok(Some(line)) => parse(line).map_err(StreamError::from),
+                Ok(None) => return None,
+                Err(e) => Err(StreamError::from(e)),
+            };
+            failed = parsed.is_err();
+            Some(parsed)
+        })
+    }
+
+    /// The characters of the next line, read one at a time, for inputs that are a single
+    /// line too long to hold in memory. Each byte is taken to be an ASCII character.
+    pub fn line_chars(&mut self) -> impl Iterator<Item = io::Result<char>> + '_ {
+        let mut bom = [0; 3];
+        let bom = BOM.encode_utf8(&mut bom).as_bytes();
+        // An error here will be hit again by the first read below.
+        if self.line_number == 0 && self.reader.fill_buf().is_ok_and(|b| b.starts_with(bom)) {
+            self.reader.consume(bom.len());
+        }
+        (&mut self.reader)
+            .bytes()
+            .map(|byte| byte.map(char::from))
+            .take_while(|c| !matches!(c, Ok('\n' | '\r')))
+    }
+
+    /// Call `f` with each remaining line of the input, stopping at the first error.
+    pub fn try_for_each(
+        &mut self,
+        mut f: impl FnMut(Source<'_>) -> Result<(), ParseError>,
+    ) -> Result<(), StreamError> {
+        while let Some(line) = self.next_line()? {
+            f(line)?;
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(false);
+        }
+        self.line_number += 1;
+        if self.line_number == 1 && self.line.starts_with(BOM) {
+            self.line.drain(..BOM.len_utf8());
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{self, normalize};
+
+    /// Each line the reader hands out, with the line number it reports errors at.
+    fn streamed(text: &str) -> Vec<(usize, String)> {
+        let mut reader = LineReader::new("input.txt", text.as_bytes());
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().unwrap() {
+            let line_number = line.error_at(line.text(), "anything").line;
+            lines.push((line_number, line.text().to_string()));
+        }
+        lines
+    }
+
+    /// The lines of the whole input, read the usual way, numbered from 1.
+    fn read_whole(text: &str) -> Vec<(usize, String)> {
+        let text = normalize(text);
+        input::lines(&text)
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn hands_out_blank_lines_only_once_another_line_follows() {
+        let text = "a\n\n\nb\n\n\n";
+        assert_eq!(
+            streamed(text),
+            [(1, "a"), (2, ""), (3, ""), (4, "b")].map(|(n, l)| (n, l.to_string()))
+        );
+        assert_eq!(streamed(text), read_whole(text));
+
+        // Unlike a whole input, an empty one has no lines at all.
+        assert!(streamed("").is_empty());
+        assert!(streamed("\n \n").is_empty());
+    }
+
+    #[test]
+    fn strips_a_byte_order_mark_line_endings_and_trailing_whitespace() {
+        for text in [
+            "\u{feff}a \r\nb\t\r\n\r\nc\r\n",
+            "\u{feff}\r\n\r\nx",
+            "1\n2 \r\n \n3",
+        ] {
+            assert_eq!(streamed(text), read_whole(text), "{text:?}");
+        }
+        assert_eq!(streamed("\u{feff}a\r\n")[0].1, "a");
+    }
+
+    #[test]
+    fn blank_lines_separate_the_same_paragraphs() {
+        let text = "1\n2\n\n\n3\n\n4 \r\n\r\n";
+        let mut streamed_paragraphs = vec![vec![]];
+        for (_, line) in streamed(text) {
+            if line.is_empty() {
+                if !streamed_paragraphs.last().unwrap().is_empty() {
+                    streamed_paragraphs.push(vec![]);
+                }
+            } else {
+                streamed_paragraphs.last_mut().unwrap().push(line);
+            }
+        }
+
+        let text = normalize(text);
+        let paragraphs: Vec<Vec<_>> = input::paragraphs(&text)
+            .map(|paragraph| input::lines(paragraph).map(str::to_string).collect())
+            .collect();
+        assert_eq!(streamed_paragraphs, paragraphs);
+    }
+
+    #[test]
+    fn reads_the_characters_of_the_first_line() {
+        for (text, chars) in [
+            ("\u{feff}abc\r\nrest\n", "abc"),
+            ("abc\nrest\n", "abc"),
+            ("xyz", "xyz"),
+            ("", ""),
+        ] {
+            let mut reader = LineReader::new("input.txt", text.as_bytes());
+            let line: String = reader.line_chars().map(Result::unwrap).collect();
+            assert_eq!(line, chars, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Stepping a simulation backwards and forwards, for debugging the days that simulate
+//! something one step at a time: jumping to a step, going back a few, and comparing two.
+//!
+//! A [`Timeline`] keeps a copy of the simulation every [`Timeline::INTERVAL`] steps. Going
+//! back restores the last copy before the step it's going to and simulates forward from
+//! there, so going back never replays more than `INTERVAL` steps, and the simulation doesn't
+//! need to know how to undo a step. Simulations whose state grows as they go should share
+//! what earlier steps built between their copies, so that the copies stay cheap.
+//!
+//! Some simulations never finish, so a timeline doesn't go past [`Timeline::MAX_STEP`].
+
+use crate::repl::{self, Repl};
+
+/// A simulation that a [`Timeline`] can step through.
+pub trait Simulation: Clone {
+    /// Advance the simulation by one step, or return `false` if it has finished.
+    fn step(&mut self) -> bool;
+
+    /// Draw the current state.
+    fn render(&self) -> String;
+
+    /// What the REPL shows of the current state, for simulations whose drawings grow too big
+    /// to read in full. Diffs still compare the whole drawings.
+    fn render_brief(&self) -> String {
+        self.render()
+    }
+}
+
+/// A simulation, with copies of earlier states for going back to them.
+#[derive(Debug, Clone)]
+pub struct Timeline<S> {
+    state: S,
+    step: usize,
+
+    /// The state at every multiple of `INTERVAL` steps that's been reached, from step 0.
+    checkpoints: Vec<S>,
+
+    /// The step the simulation finished on, once it's got there.
+    last_step: Option<usize>,
+}
+
+impl<S: Simulation> Timeline<S> {
+    /// How many steps apart the copies of the simulation are.
+    pub const INTERVAL: usize = 100;
+
+    /// The furthest step a timeline goes to, so that asking for a step far past the end of a
+    /// simulation that never finishes gives up instead of running forever.
+    pub const MAX_STEP: usize = 1_000_000;
+
+    pub fn new(simulation: S) -> Self {
+        Self {
+            checkpoints: vec![simulation.clone()],
+            state: simulation,
+            step: 0,
+            last_step: None,
+        }
+    }
+
+    /// The state of the simulation at the current step.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn current_step(&self) -> usize {
+        self.step
+    }
+
+    /// The step the simulation finished on, if it's been run that far.
+    pub fn last_step(&self) -> Option<usize> {
+        self.last_step
+    }
+
+    /// Step forwards, stopping early if the simulation finishes or gets to `MAX_STEP`.
+    pub fn forward(&mut self, steps: usize) {
+        for _ in 0..steps {
+            if self.last_step == Some(self.step) || self.step == Self::MAX_STEP {
+                return;
+            }
+            if !self.state.step() {
+                self.last_step = Some(self.step);
+                return;
+            }
+            self.step += 1;
+            if self.step == self.checkpoints.len() * Self::INTERVAL {
+                self.checkpoints.push(self.state.clone());
+            }
+        }
+    }
+
+    /// Step backwards, stopping at the start.
+    pub fn back(&mut self, steps: usize) {
+        self.goto(self.step.saturating_sub(steps));
+    }
+
+    /// Go to a step, or to the last one if the simulation finishes before it, or to
+    /// `MAX_STEP` if the step is past that.
+    pub fn goto(&mut self, step: usize) {
+        let step = step.min(Self::MAX_STEP);
+        let checkpoint = (step / Self::INTERVAL).min(self.checkpoints.len() - 1);
+        if step < self.step || checkpoint * Self::INTERVAL > self.step {
+            self.state = self.checkpoints[checkpoint].clone();
+            self.step = checkpoint * Self::INTERVAL;
+        }
+        self.forward(step - self.step);
+    }
+
+    /// Draw the simulation at a step, then come back to the current one.
+    pub fn render_at(&mut self, step: usize) -> String {
+        let current = self.step;
+        self.goto(step);
+        let rendered = self.state.render();
+        self.goto(current);
+        rendered
+    }
+}
+
+/// The rows that differ between two drawings of a simulation, as the row number followed by
+/// the row before, marked with `-`, and after, marked with `+`. The drawings are lined up at
+/// the bottom, and the rows numbered from 1 at the bottom, since the simulations that grow,
+/// like stacks of crates and towers of rocks, grow upwards.
+pub fn diff(before: &str, after: &str) -> String {
+    let (before, after): (Vec<_>, Vec<_>) = (before.lines().collect(), after.lines().collect());
+    let height = before.len().max(after.len());
+    let mut result = String::new();
+    for row in (1..=height).rev() {
+        let old = before.len().checked_sub(row).map(|idx| before[idx]);
+        let new = after.len().checked_sub(row).map(|idx| after[idx]);
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            result += &format!("{row:>4} - {old}\n");
+        }
+        if let Some(new) = new {
+            result += &format!("{row:>4} + {new}\n");
+        }
+    }
+    result
+}
+
+/// The current step of a timeline, drawn, with where it is in the simulation.
+fn show<S: Simulation>(timeline: &Timeline<S>) -> String {
+    let position = match timeline.last_step() {
+        Some(last) if last == timeline.current_step() => format!("step {last}, the last one"),
+        Some(last) => format!("step {} of {last}", timeline.current_step()),
+        None if timeline.current_step() == Timeline::<S>::MAX_STEP => {
+            format!("step {}, as far as it goes", timeline.current_step())
+        }
+        None => format!("step {}", timeline.current_step()),
+    };
+    format!("{}\n{position}", timeline.state().render_brief().trim_end())
+}
+
+/// A REPL for stepping through a simulation, which days can add commands of their own to.
+pub fn repl<'a, S: Simulation + 'a>(day: u32, simulation: S) -> Repl<'a, Timeline<S>> {
+    Repl::new(day, Timeline::new(simulation))
+        .command("show", "", "show the current step", |timeline, _| {
+            Ok(show(timeline))
+        })
+        .command("step", "[steps]", "step forwards", |timeline, args| {
+            let steps = match args {
+                [] => 1,
+                _ => repl::arg(args, 0, "a number of steps")?,
+            };
+            timeline.forward(steps);
+            Ok(show(timeline))
+        })
+        .command("back", "[steps]", "step backwards", |timeline, args| {
+            let steps = match args {
+                [] => 1,
+                _ => repl::arg(args, 0, "a number of steps")?,
+            };
+            timeline.back(steps);
+            Ok(show(timeline))
+        })
+        .command("goto", "<step>", "go to a step", |timeline, args| {
+            timeline.goto(repl::arg(args, 0, "a step number")?);
+            Ok(show(timeline))
+        })
+        .command(
+            "diff",
+            "<step> <step>",
+            "show the rows that differ between two steps",
+            |timeline, args| {
+                let before = repl::arg(args, 0, "a step number")?;
+                let after = repl::arg(args, 1, "a step number")?;
+                let changes = diff(&timeline.render_at(before), &timeline.render_at(after));
+                if changes.is_empty() {
+                    return Ok("no differences".to_string());
+                }
+                Ok(changes)
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to a limit, remembering how many steps it's been simulated for in all.
+    #[derive(Debug, Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+        simulated: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+            self.value += 1;
+            self.simulated.set(self.simulated.get() + 1);
+            true
+        }
+
+        fn render(&self) -> String {
+            format!("{}\n", self.value)
+        }
+    }
+
+    #[test]
+    fn goes_back_from_the_last_checkpoint() {
+        let counter = Counter {
+            value: 0,
+            limit: 250,
+            simulated: Default::default(),
+        };
+        let simulated = counter.simulated.clone();
+        let mut timeline = Timeline::new(counter);
+
+        timeline.goto(230);
+        assert_eq!(
+            (timeline.current_step(), timeline.state().value),
+            (230, 230)
+        );
+        timeline.back(20);
+        assert_eq!(timeline.state().value, 210);
+        assert_eq!(simulated.get(), 240);
+
+        timeline.back(300);
+        assert_eq!(timeline.state().value, 0);
+        timeline.goto(usize::MAX);
+        assert_eq!(timeline.last_step(), Some(250));
+        assert_eq!(timeline.state().value, 250);
+
+        assert_eq!(
+            diff(&timeline.render_at(3), &timeline.render_at(4)),
+            "   1 - 3\n   1 + 4\n"
+        );
+        assert_eq!(timeline.current_step(), 250);
+    }
+
+    #[test]
+    fn stops_at_the_last_step_it_goes_to() {
+        let counter = Counter {
+            value: 0,
+            limit: usize::MAX,
+            simulated: Default::default(),
+        };
+        let mut timeline = Timeline::new(counter);
+        timeline.goto(usize::MAX);
+        assert_eq!(timeline.current_step(), Timeline::<Counter>::MAX_STEP);
+        assert_eq!(timeline.last_step(), None);
+        assert!(show(&timeline).ends_with("as far as it goes"));
+
+        timeline.forward(10);
+        assert_eq!(timeline.state().value, Timeline::<Counter>::MAX_STEP);
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    fs,
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+use crate::image::{self, PngExport};
+
+/// The states of a simulation, each rendered as text, recorded while a solution runs.
+///
+/// Rendering is skipped entirely unless recording was asked for, and only the final state is
+/// rendered if that's all that's needed. Long simulations keep at
+/// most [`Frames::LIMIT`] frames: once that many are recorded, every other one is dropped
+/// and only every other step is recorded from then on, so the frames stay evenly spaced.
+#[derive(Debug, Clone)]
+pub struct Frames {
+    frames: Vec<String>,
+    recording: bool,
+    recording_steps: bool,
+    steps: usize,
+    stride: usize,
+}
+
+impl Frames {
+    pub const LIMIT: usize = 1000;
+
+    pub fn disabled() -> Self {
+        Self {
+            frames: Vec::new(),
+            recording: false,
+            recording_steps: false,
+            steps: 0,
+            stride: 1,
+        }
+    }
+
+    pub fn recording() -> Self {
+        Self {
+            recording: true,
+            recording_steps: true,
+            ..Self::disabled()
+        }
+    }
+
+    /// Frames that only record the final state, passed to [`Frames::finish`].
+    pub fn final_only() -> Self {
+        Self {
+            recording: true,
+            ..Self::disabled()
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Record the next step of the simulation, if it's one that is being kept.
+    pub fn push(&mut self, render: impl FnOnce() -> String) {
+        if !self.recording_steps {
+            return;
+        }
+
+        let step = self.steps;
+        self.steps += 1;
+        if !step.is_multiple_of(self.stride) {
+            return;
+        }
+
+        if self.frames.len() == Self::LIMIT {
+            let mut idx = 0;
+            self.frames.retain(|_| {
+                idx += 1;
+                idx % 2 == 1
+            });
+            self.stride *= 2;
+            if !step.is_multiple_of(self.stride) {
+                return;
+            }
+        }
+        self.frames.push(render());
+    }
+
+    /// Record the final state of the simulation, even if its step wouldn't otherwise be kept.
+    pub fn finish(&mut self, render: impl FnOnce() -> String) {
+        if !self.recording {
+            return;
+        }
+
+        let frame = render();
+        if self.frames.last() != Some(&frame) {
+            self.frames.push(frame);
+        }
+    }
+
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+}
+
+/// What to do with the frames of a simulation, chosen on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visualize {
+    /// Don't record any frames.
+    Off,
+
+    /// Play the frames back in the terminal once the solution finishes.
+    Play,
+
+    /// Write the frames to a text file, for when there's no terminal to play them in.
+    WriteTo(String),
+
+    /// Write the frames to an animated GIF.
+    Gif(String),
+
+    /// Write a picture of the final state to a PNG file.
+    Png(PngExport),
+}
+
+impl Visualize {
+    /// Take the `--visualize`, `--visualize-to <file>`, `--gif <file>` and `--png <file>`
+    /// options out of the command line arguments, leaving the positional arguments in place.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        if let Some(export) = PngExport::from_args(args) {
+            Visualize::Png(export)
+        } else if let Some(idx) = args.iter().position(|arg| arg == "--gif") {
+            args.remove(idx);
+            let path = args.get(idx).cloned().expect("animation file");
+            args.remove(idx);
+            Visualize::Gif(path)
+        } else if let Some(idx) = args.iter().position(|arg| arg == "--visualize-to") {
+            args.remove(idx);
+            let path = args.get(idx).cloned().expect("frames file");
+            args.remove(idx);
+            Visualize::WriteTo(path)
+        } else if let Some(idx) = args.iter().position(|arg| arg == "--visualize") {
+            args.remove(idx);
+            Visualize::Play
+        } else {
+            Visualize::Off
+        }
+    }
+
+    /// Frames that are recording if they'll be shown, and disabled otherwise.
+    pub fn frames(&self) -> Frames {
+        match self {
+            Visualize::Off => Frames::disabled(),
+            Visualize::Play | Visualize::WriteTo(_) | Visualize::Gif(_) => Frames::recording(),
+            Visualize::Png(_) => Frames::final_only(),
+        }
+    }
+
+    pub fn show(&self, frames: &Frames) -> io::Result<()> {
+        match self {
+            Visualize::Off => Ok(()),
+            Visualize::Play => play(frames.frames()),
+            Visualize::WriteTo(path) => write_frames(path, frames.frames()),
+            Visualize::Gif(path) => {
+                let grids: Vec<_> = frames
+                    .frames()
+                    .iter()
+                    .map(|f| image::text_grid(f))
+                    .collect();
+                image::write_gif(path, &grids, image::text_color, GIF_FRAME_DELAY_MS)
+            }
+            Visualize::Png(export) => match frames.frames().last() {
+                Some(frame) => export.write(&image::text_grid(frame), image::text_color),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+/// Write each frame to a text file, under a header line with its number.
+pub fn write_frames(path: &str, frames: &[String]) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    for (idx, frame) in frames.iter().enumerate() {
+        writeln!(file, "--- frame {} of {} ---", idx + 1, frames.len())?;
+        writeln!(file, "{}", frame.trim_end_matches('\n'))?;
+    }
+    file.flush()
+}
+
+/// The delay between frames of an animated GIF.
+const GIF_FRAME_DELAY_MS: u16 = 50;
+
+/// The delay between frames at each playback speed, from slowest to fastest.
+const FRAME_DELAYS_MS: [u64; 7] = [1000, 500, 200, 100, 50, 20, 5];
+
+/// Puts the terminal back the way it was, even if playback fails partway through.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Play the frames back in the terminal.
+///
+/// Space pauses and resumes, the left and right arrows step back and forward a frame,
+/// Home and End jump to the first and last frames, `+` and `-` change the speed,
+/// and `q` or Escape quits.
+pub fn play(frames: &[String]) -> io::Result<()> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+
+    let mut current = 0;
+    let mut playing = true;
+    let mut speed = 3;
+
+    loop {
+        draw_frame(&mut stdout, frames, current, playing, speed)?;
+
+        let delay = Duration::from_millis(FRAME_DELAYS_MS[speed]);
+        if playing && !event::poll(delay)? {
+            if current + 1 < frames.len() {
+                current += 1;
+            } else {
+                playing = false;
+            }
+            continue;
+        }
+
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char(' ') => {
+                if !playing && current + 1 == frames.len() {
+                    current = 0;
+                }
+                playing = !playing;
+            }
+            KeyCode::Right => {
+                playing = false;
+                current = (current + 1).min(frames.len() - 1);
+            }
+            KeyCode::Left => {
+                playing = false;
+                current = current.saturating_sub(1);
+            }
+            KeyCode::Home => current = 0,
+            KeyCode::End => current = frames.len() - 1,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                speed = (speed + 1).min(FRAME_DELAYS_MS.len() - 1)
+            }
+            KeyCode::Char('-') => speed = speed.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn draw_frame(
+    stdout: &mut io::Stdout,
+    frames: &[String],
+    current: usize,
+    playing: bool,
+    speed: usize,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = width as usize;
+    let max_rows = (height as usize).saturating_sub(1);
+
+    queue!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+    for line in frames[current].lines().take(max_rows) {
+        let line: String = line.chars().take(width).collect();
+        queue!(stdout, Print(line), Print("\r\n"))?;
+    }
+
+    let status = format!(
+        "frame {}/{}  {}  {}ms/frame  [space] play/pause  [←/→] step  [+/-] speed  [q] quit",
+        current + 1,
+        frames.len(),
+        if playing { "playing" } else { "paused" },
+        FRAME_DELAYS_MS[speed],
+    );
+    let status: String = status.chars().take(width).collect();
+    queue!(stdout, cursor::MoveTo(0, max_rows as u16), Print(status))?;
+    stdout.flush()
+}
@@ -0,0 +1,75 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use common::input;
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 1)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Vec<u64>>, ParseError> {
+    source
+        .paragraphs()
+        .map(|x| {
+            input::lines(x)
+                .map(|x| source.parse_at(x, "a calorie count"))
+                .collect()
+        })
+        .collect()
+}
+
+#[aoc(day = 1, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &[Vec<u64>]) -> u64 {
+    data.iter().map(|x| x.iter().sum()).max().unwrap()
+}
+
+#[aoc(day = 1, part = 2, params(top = 3), stream = stream_part2)]
+pub fn solve_part2(data: &[Vec<u64>], top: usize) -> u64 {
+    data.iter()
+        .map(|x| x.iter().sum::<u64>())
+        .sorted_unstable()
+        .rev()
+        .take(top)
+        .sum()
+}
+
+/// Call `on_elf` with the total calories carried by each elf, reading the input a line
+/// at a time.
+fn stream_elf_totals<R: BufRead>(
+    lines: &mut LineReader<R>,
+    mut on_elf: impl FnMut(u64),
+) -> Result<(), StreamError> {
+    let mut total = None;
+    lines.try_for_each(|line| {
+        if line.text().is_empty() {
+            let elf_total = total
+                .take()
+                .ok_or_else(|| line.error_at(line.text(), "a calorie count"))?;
+            on_elf(elf_total);
+        } else {
+            *total.get_or_insert(0) += line.parse_at::<u64>(line.text(), "a calorie count")?;
+        }
+        Ok(())
+    })?;
+    total.into_iter().for_each(on_elf);
+    Ok(())
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<u64, StreamError> {
+    let mut max = None;
+    stream_elf_totals(lines, |elf_total| max = max.max(Some(elf_total)))?;
+    Ok(max.expect("no elves in the input"))
+}
+
+pub fn stream_part2<R: BufRead>(lines: &mut LineReader<R>, top: usize) -> Result<u64, StreamError> {
+    // The smallest of the top totals so far is at the top of the heap, ready to be replaced.
+    let mut top_totals = BinaryHeap::with_capacity(top + 1);
+    stream_elf_totals(lines, |elf_total| {
+        top_totals.push(Reverse(elf_total));
+        if top_totals.len() > top {
+            top_totals.pop();
+        }
+    })?;
+    Ok(top_totals.into_iter().map(|Reverse(total)| total).sum())
+}
@@ -0,0 +1,166 @@
+use std::io::{self, BufRead};
+
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[allow(unused_imports)]
+use nom::Parser;
+
+#[allow(unused_imports)]
+use nom_supreme::parser_ext::ParserExt;
+
+use common::image::{self, PngExport};
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+use itertools::process_results;
+use nom::{branch::alt, character::complete::space1};
+use nom_supreme::tag::complete::tag;
+use tracing::debug;
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Addx(i64),
+    Noop,
+}
+
+impl Instruction {
+    fn parse(source: Source<'_>, input: &str) -> Result<Self, ParseError> {
+        let mut parser = alt((
+            tag("noop").value(Instruction::Noop),
+            tag("addx")
+                .precedes(space1)
+                .precedes(nom::character::complete::i64::<&str, nom::error::Error<&str>>)
+                .map(Instruction::Addx),
+        ))
+        .complete();
+
+        match parser.parse(input) {
+            Ok(("", result)) => Ok(result),
+            Ok((remainder, _)) => Err(source.error_at(remainder, "end of line")),
+            Err(e) => Err(source.nom_error(e)),
+        }
+    }
+}
+
+#[aoc_parser(day = 10)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Instruction>, ParseError> {
+    source
+        .lines()
+        .map(|line| Instruction::parse(source, line))
+        .collect()
+}
+
+#[aoc(day = 10, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &[Instruction]) -> i64 {
+    signal_strength(data.iter().cloned())
+}
+
+#[aoc(day = 10, part = 2, stream = stream_part2)]
+pub fn solve_part2(data: &[Instruction]) -> String {
+    draw_screen(data.iter().cloned())
+}
+
+/// Draw the screen after running the program, with the lit pixels in green.
+pub fn draw_screen_png(data: &[Instruction], png: &PngExport) -> io::Result<()> {
+    let screen = solve_part2(data);
+    png.write(&image::text_grid(&screen), |pixel| match pixel {
+        b'#' => [120, 255, 120],
+        _ => image::BACKGROUND,
+    })
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<i64, StreamError> {
+    process_results(
+        lines.parse_lines(|line| Instruction::parse(line, line.text())),
+        |instructions| signal_strength(instructions),
+    )
+}
+
+pub fn stream_part2<R: BufRead>(lines: &mut LineReader<R>) -> Result<String, StreamError> {
+    process_results(
+        lines.parse_lines(|line| Instruction::parse(line, line.text())),
+        |instructions| draw_screen(instructions),
+    )
+}
+
+fn signal_strength(data: impl IntoIterator<Item = Instruction>) -> i64 {
+    let mut x_register = 1i64;
+
+    let signal_strength_reading_stride = 40;
+    let mut cycles_remaining = 20;
+    let mut cycle_counter = 0;
+    let mut result = 0;
+
+    for instr in data {
+        let (addend, latency) = match instr {
+            Instruction::Addx(n) => (n, 2),
+            Instruction::Noop => (0, 1),
+        };
+
+        assert!(cycles_remaining >= 0);
+        if cycles_remaining <= latency {
+            result += (cycle_counter + cycles_remaining) * x_register;
+            debug!(
+                cycle = cycle_counter + cycles_remaining,
+                x_register, result, "read signal strength"
+            );
+            cycles_remaining += signal_strength_reading_stride;
+        }
+
+        cycles_remaining -= latency;
+        cycle_counter += latency;
+        x_register += addend;
+        debug!(cycle = cycle_counter, x_register, "executed {instr:?}");
+    }
+
+    result
+}
+
+fn draw_screen(data: impl IntoIterator<Item = Instruction>) -> String {
+    let mut x_register = 1i64;
+    let mut result = String::with_capacity(250);
+
+    let screen_width = 40;
+    let mut cycle_counter = 0;
+
+    for instr in data {
+        let (addend, latency) = match instr {
+            Instruction::Addx(n) => (n, 2),
+            Instruction::Noop => (0, 1),
+        };
+
+        for _ in 0..latency {
+            let next_char = if ((x_register - 1)..=(x_register + 1)).contains(&cycle_counter) {
+                "#"
+            } else {
+                "."
+            };
+            cycle_counter += 1;
+            result += next_char;
+
+            if cycle_counter == screen_width {
+                result += "\n";
+                cycle_counter -= screen_width
+            }
+        }
+
+        x_register += addend;
+        debug!(cycle = cycle_counter, x_register, "executed {instr:?}");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse::Source;
+
+    use super::{parse_input, solve_part2};
+
+    #[test]
+    fn draws_the_larger_example() {
+        let data = parse_input(Source::new("example2.txt", include_str!("../example2.txt")));
+        insta::assert_snapshot!(solve_part2(&data.unwrap()));
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+
+use common::parse::Source;
+use day11::MonkeyBusiness;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Err(e) = MonkeyBusiness::parse(Source::new("fuzz", text)) {
+            // Rendering the error must not panic either.
+            let _ = e.to_string();
+        }
+    }
+});
@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, VecDeque};
+
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[allow(unused_imports)]
+use nom::Parser;
+
+#[allow(unused_imports)]
+use nom_supreme::parser_ext::ParserExt;
+
+use common::num::{ArithmeticError, Int};
+use common::parse::{ParseError, Source};
+use common::{aoc, aoc_parser};
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, multispace0, multispace1, space0, space1},
+    combinator::consumed,
+    multi::many1,
+    sequence::tuple,
+    IResult,
+};
+use nom_supreme::{error::ErrorTree, tag::complete::tag};
+use tracing::debug;
+
+fn parse_number(input: &str) -> IResult<&str, i64, ErrorTree<&str>> {
+    digit1.parse_from_str().parse(input)
+}
+
+#[derive(Debug, Clone)]
+pub enum Operand {
+    Input,
+    Literal(i64),
+}
+
+impl Operand {
+    fn value<N: Int>(&self, input: &N) -> N {
+        match self {
+            Operand::Input => input.clone(),
+            Operand::Literal(x) => N::from_i64(*x),
+        }
+    }
+
+    fn parse(input: &str) -> IResult<&str, Self, ErrorTree<&str>> {
+        alt((
+            tag("old").map(|_| Operand::Input),
+            parse_number.map(Operand::Literal),
+        ))(input)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Operation {
+    Add(Operand),
+    Mul(Operand),
+}
+
+impl Operation {
+    pub fn apply<N: Int>(&self, input: &N) -> Result<N, ArithmeticError> {
+        match self {
+            Operation::Add(operand) => input.checked_add(&operand.value(input)),
+            Operation::Mul(operand) => input.checked_mul(&operand.value(input)),
+        }
+    }
+
+    fn parse(input: &str) -> IResult<&str, Self, ErrorTree<&str>> {
+        tag("new = old ")
+            .precedes(alt((
+                char('+')
+                    .precedes(space1)
+                    .precedes(Operand::parse)
+                    .map(Operation::Add),
+                char('*')
+                    .precedes(space1)
+                    .precedes(Operand::parse)
+                    .map(Operation::Mul),
+            )))
+            .parse(input)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey<N = i64> {
+    pub queue: VecDeque<N>,
+    pub operation: Operation,
+    pub test_divisor: i64,
+    pub target_when_true: i64,
+    pub target_when_false: i64,
+}
+
+/// The monkeys and the items they're holding, with worry levels held in `N`.
+#[derive(Debug, Clone)]
+pub struct MonkeyBusiness<N = i64> {
+    pub monkeys: BTreeMap<i64, Monkey<N>>,
+    pub items_inspected: BTreeMap<i64, usize>,
+}
+
+impl MonkeyBusiness {
+    pub fn parse(source: Source<'_>) -> Result<Self, ParseError> {
+        let mut monkey_parser = tuple((
+            tag("Monkey")
+                .terminated(space1)
+                .precedes(consumed(parse_number).terminated(char(':')))
+                .terminated(multispace1),
+            tag("Starting items: ").precedes(
+                many1(parse_number.terminated(char(',').terminated(space0).opt()))
+                    .terminated(multispace1),
+            ),
+            tag("Operation: ")
+                .precedes(Operation::parse)
+                .terminated(multispace1),
+            tag("Test: divisible by ")
+                .precedes(consumed(parse_number))
+                .terminated(multispace1),
+            tag("If true: throw to monkey ")
+                .precedes(consumed(parse_number))
+                .terminated(multispace1),
+            tag("If false: throw to monkey ")
+                .precedes(consumed(parse_number))
+                .terminated(multispace0),
+        ));
+
+        let mut monkeys = BTreeMap::new();
+        let mut targets = vec![];
+
+        for paragraph in source.paragraphs() {
+            let (
+                rest,
+                (id, starting_items, operation, test_divisor, target_when_true, target_when_false),
+            ) = monkey_parser
+                .parse(paragraph)
+                .map_err(|e| source.error_tree(e))?;
+            if !rest.is_empty() {
+                return Err(source.error_at(rest, "a blank line before the next monkey"));
+            }
+
+            // Monkeys take their turns in order of their ids, so the ids must be sequential.
+            let (id_fragment, id) = id;
+            if id != monkeys.len() as i64 {
+                let expected = format!("monkey id {}", monkeys.len());
+                return Err(source.error_at(id_fragment, expected));
+            }
+
+            let (divisor_fragment, test_divisor) = test_divisor;
+            if test_divisor == 0 {
+                return Err(source.error_at(divisor_fragment, "a non-zero divisor"));
+            }
+
+            targets.extend([target_when_true, target_when_false]);
+            let monkey = Monkey {
+                queue: starting_items.into(),
+                operation,
+                test_divisor,
+                target_when_true: target_when_true.1,
+                target_when_false: target_when_false.1,
+            };
+            monkeys.insert(id, monkey);
+        }
+
+        for (target_fragment, target) in targets {
+            if !monkeys.contains_key(&target) {
+                return Err(source.error_at(target_fragment, "the id of an existing monkey"));
+            }
+        }
+
+        Ok(Self::new(monkeys))
+    }
+
+    /// The same monkeys, with worry levels held in another integer type.
+    pub fn into_int<N: Int>(self) -> MonkeyBusiness<N> {
+        let monkeys = self
+            .monkeys
+            .into_iter()
+            .map(|(id, monkey)| {
+                let monkey = Monkey {
+                    queue: monkey.queue.into_iter().map(N::from_i64).collect(),
+                    operation: monkey.operation,
+                    test_divisor: monkey.test_divisor,
+                    target_when_true: monkey.target_when_true,
+                    target_when_false: monkey.target_when_false,
+                };
+                (id, monkey)
+            })
+            .collect();
+        MonkeyBusiness::new(monkeys)
+    }
+}
+
+impl<N: Int> MonkeyBusiness<N> {
+    pub fn new(monkeys: BTreeMap<i64, Monkey<N>>) -> Self {
+        Self {
+            items_inspected: monkeys.keys().map(|k| (*k, 0)).collect(),
+            monkeys,
+        }
+    }
+
+    pub fn simulate_monkey(
+        &mut self,
+        id: i64,
+        worry_reduction: &impl Fn(N) -> Result<N, ArithmeticError>,
+    ) -> Result<(), ArithmeticError> {
+        while let Some(item_value) = self.monkeys.get_mut(&id).unwrap().queue.pop_front() {
+            let monkey = &self.monkeys[&id];
+            let new_worry = worry_reduction(monkey.operation.apply(&item_value)?)?;
+
+            let remainder = new_worry.checked_rem(&N::from_i64(monkey.test_divisor))?;
+            let next_id = if remainder.is_zero() {
+                monkey.target_when_true
+            } else {
+                monkey.target_when_false
+            };
+            self.monkeys
+                .get_mut(&next_id)
+                .unwrap()
+                .queue
+                .push_back(new_worry);
+
+            self.items_inspected.entry(id).and_modify(|x| *x += 1);
+        }
+        Ok(())
+    }
+
+    pub fn simulate_round(
+        &mut self,
+        worry_reduction: impl Fn(N) -> Result<N, ArithmeticError>,
+    ) -> Result<(), ArithmeticError> {
+        for index in 0..self.monkeys.len() {
+            self.simulate_monkey(index as i64, &worry_reduction)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_monkey_business(&self) -> usize {
+        let most_items = *self.items_inspected.values().max().unwrap();
+        let (_, &mut second_most_items, _) = self
+            .items_inspected
+            .values()
+            .collect_vec()
+            .select_nth_unstable(self.items_inspected.len() - 2);
+
+        most_items * second_most_items
+    }
+}
+
+#[aoc_parser(day = 11)]
+pub fn parse_input(source: Source<'_>) -> Result<MonkeyBusiness, ParseError> {
+    MonkeyBusiness::parse(source)
+}
+
+#[aoc(day = 11, part = 1, params(rounds = 20))]
+pub fn solve_part1<N: Int>(data: MonkeyBusiness, rounds: usize) -> Result<usize, ArithmeticError> {
+    let mut data = data.into_int::<N>();
+    let three = N::from_i64(3);
+    for round in 1..=rounds {
+        data.simulate_round(|worry| worry.checked_div(&three))?;
+        debug!(round, "finished round");
+    }
+
+    Ok(data.get_monkey_business())
+}
+
+pub fn gcd<N: Int>(a: N, b: N) -> Result<N, ArithmeticError> {
+    if b.is_zero() {
+        Ok(a)
+    } else if b == N::from_i64(1) {
+        Ok(b)
+    } else if a < b {
+        gcd(b, a)
+    } else {
+        let remainder = a.checked_rem(&b)?;
+        gcd(b, remainder)
+    }
+}
+
+pub fn lcm<N: Int>(a: N, b: N) -> Result<N, ArithmeticError> {
+    let common = gcd(a.clone(), b.clone())?;
+    a.checked_div(&common)?.checked_mul(&b)
+}
+
+#[aoc(day = 11, part = 2, params(rounds = 10000))]
+pub fn solve_part2<N: Int>(data: MonkeyBusiness, rounds: usize) -> Result<usize, ArithmeticError> {
+    let mut data = data.into_int::<N>();
+    let divisors_lcm = data
+        .monkeys
+        .values()
+        .map(|m| N::from_i64(m.test_divisor))
+        .try_fold(N::from_i64(1), lcm)?;
+
+    for round in 1..=rounds {
+        data.simulate_round(|worry| worry.checked_rem(&divisors_lcm))?;
+        debug!(round, "finished round");
+    }
+
+    Ok(data.get_monkey_business())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::num::{ArithmeticError, BigInt};
+    use common::parse::Source;
+
+    use super::{parse_input, solve_part1, solve_part2, MonkeyBusiness};
+
+    /// Two monkeys that square an item's worry level back and forth, which outgrows an `i64`
+    /// in the first round of part 1, and an `i128` in the second.
+    const SQUARING: &str = "\
+Monkey 0:
+  Starting items: 1000000
+  Operation: new = old * old
+  Test: divisible by 7
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 2
+  Operation: new = old * old
+  Test: divisible by 5
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+
+    fn parse(text: &str) -> MonkeyBusiness {
+        parse_input(Source::new("input.txt", text)).unwrap()
+    }
+
+    #[test]
+    fn example() {
+        let monkeys = parse(include_str!("../example.txt"));
+        assert_eq!(solve_part1::<i64>(monkeys.clone(), 20), Ok(10605));
+        assert_eq!(solve_part2::<i64>(monkeys, 10000), Ok(2713310158));
+    }
+
+    #[test]
+    fn overflowing_i64_is_an_error_and_wider_types_succeed() {
+        let error = solve_part1::<i64>(parse(SQUARING), 1).unwrap_err();
+        assert!(matches!(
+            error,
+            ArithmeticError::Overflow {
+                int_type: "i64",
+                ..
+            }
+        ));
+        assert_eq!(solve_part1::<i128>(parse(SQUARING), 1), Ok(2));
+
+        let error = solve_part1::<i128>(parse(SQUARING), 2).unwrap_err();
+        assert!(matches!(
+            error,
+            ArithmeticError::Overflow {
+                int_type: "i128",
+                ..
+            }
+        ));
+        assert_eq!(solve_part1::<BigInt>(parse(SQUARING), 3), Ok(30));
+
+        // Part 2 keeps the worry levels below the product of the divisors.
+        assert_eq!(solve_part2::<i64>(parse(SQUARING), 1000), Ok(1999 * 2000));
+    }
+}
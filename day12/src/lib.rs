@@ -0,0 +1,208 @@
+use std::{collections::VecDeque, io};
+
+use common::image::{self, PngExport};
+use common::parse::{ParseError, Source};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 12)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Vec<char>>, ParseError> {
+    let lines = source.lines().collect_vec();
+    let width = lines[0].len();
+
+    let data: Vec<Vec<char>> = lines
+        .into_iter()
+        .map(|x| {
+            if let Some((idx, _)) = x
+                .char_indices()
+                .find(|(_, c)| !matches!(c, 'a'..='z' | 'S' | 'E'))
+            {
+                return Err(source.error_at(&x[idx..], "a height letter, `S` or `E`"));
+            }
+            if x.len() != width {
+                let position = &x[width.min(x.len())..];
+                return Err(source.error_at(position, format!("a row {width} tiles wide")));
+            }
+            Ok(x.chars().collect())
+        })
+        .collect::<Result<_, _>>()?;
+
+    for marker in ['S', 'E'] {
+        if locate_char(&data, marker).is_none() {
+            let end = &source.text()[source.text().len()..];
+            return Err(source.error_at(end, format!("a `{marker}` somewhere in the map")));
+        }
+    }
+
+    Ok(data)
+}
+
+fn heights(data: &[Vec<char>]) -> Vec<Vec<u32>> {
+    data.iter()
+        .map(|row| {
+            row.iter()
+                .map(|c| match *c {
+                    'S' => 0,
+                    'E' => ('z' as u32) - ('a' as u32),
+                    c => (c as u32) - ('a' as u32),
+                })
+                .collect_vec()
+        })
+        .collect_vec()
+}
+
+/// The number of steps from the start to every square, or `None` if it can't be reached.
+/// Draw how far each square is from the start, as a heat map, leaving out the squares that
+/// can't be reached.
+pub fn draw_distance_map(data: &[Vec<char>], png: &PngExport) -> io::Result<()> {
+    let distances = distance_map(data);
+    let max_distance = distances.iter().flatten().flatten().max().copied();
+    let max_distance = max_distance.unwrap_or_default().max(1) as f64;
+    png.write(&distances, |distance| match distance {
+        Some(distance) => image::heat(*distance as f64 / max_distance),
+        None => image::BACKGROUND,
+    })
+}
+
+pub fn distance_map(data: &[Vec<char>]) -> Vec<Vec<Option<u64>>> {
+    let (start_i, start_j) = locate_char(data, 'S').expect("no start");
+    let heights = heights(data);
+
+    let max_i = heights.len();
+    let max_j = heights[0].len();
+
+    let dx = [0, 0, 1, -1];
+    let dy = [1, -1, 0, 0];
+
+    let mut distances = vec![vec![None; max_j]; max_i];
+    distances[start_i][start_j] = Some(0);
+
+    let mut queue = VecDeque::from([(start_i, start_j, 0)]);
+    while let Some((i, j, distance)) = queue.pop_front() {
+        for (dx, dy) in dx.iter().copied().zip(dy.iter().copied()) {
+            if let Some((next_i, next_j)) = offset_coords(i, j, dx, dy, max_i, max_j) {
+                if heights[next_i][next_j] <= heights[i][j] + 1
+                    && distances[next_i][next_j].is_none()
+                {
+                    distances[next_i][next_j] = Some(distance + 1);
+                    queue.push_back((next_i, next_j, distance + 1));
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+fn locate_char(data: &[Vec<char>], target: char) -> Option<(usize, usize)> {
+    data.iter().enumerate().find_map(|(i, row)| {
+        row.iter()
+            .find_position(|c| **c == target)
+            .map(|(j, _)| (i, j))
+    })
+}
+
+fn offset_coords(
+    x: usize,
+    y: usize,
+    dx: i64,
+    dy: i64,
+    max_x: usize,
+    max_y: usize,
+) -> Option<(usize, usize)> {
+    let new_x = (x as i64) - dx;
+    let new_y = (y as i64) - dy;
+
+    if new_x >= 0 && new_y >= 0 {
+        let new_x = new_x as usize;
+        let new_y = new_y as usize;
+        if new_x < max_x && new_y < max_y {
+            return Some((new_x, new_y));
+        }
+    }
+
+    None
+}
+
+#[aoc(day = 12, part = 1)]
+pub fn solve_part1(data: &[Vec<char>]) -> u64 {
+    let (start_i, start_j) = locate_char(data, 'S').expect("no start");
+    let (end_i, end_j) = locate_char(data, 'E').expect("no end");
+
+    let heights = heights(data);
+
+    let max_i = heights.len();
+    let max_j = heights[0].len();
+
+    let dx = [0, 0, 1, -1];
+    let dy = [1, -1, 0, 0];
+
+    let distance_row = vec![u64::MAX; max_j];
+    let mut distances = vec![distance_row; max_i];
+
+    distances[start_i][start_j] = 0;
+
+    let mut queue = VecDeque::from([(start_i, start_j)]);
+    while let Some((i, j)) = queue.pop_front() {
+        let next_distance = distances[i][j] + 1;
+
+        for (dx, dy) in dx.iter().copied().zip(dy.iter().copied()) {
+            if let Some((next_i, next_j)) = offset_coords(i, j, dx, dy, max_i, max_j) {
+                if heights[next_i][next_j] <= heights[i][j] + 1
+                    && distances[next_i][next_j] == u64::MAX
+                {
+                    if (next_i, next_j) == (end_i, end_j) {
+                        return next_distance;
+                    }
+
+                    distances[next_i][next_j] = next_distance;
+                    queue.push_back((next_i, next_j));
+                }
+            }
+        }
+    }
+
+    unreachable!("no solution found")
+}
+
+#[aoc(day = 12, part = 2)]
+pub fn solve_part2(data: &[Vec<char>]) -> u64 {
+    // Approach: start at E and go backwards to find the closest 'a' point.
+    let (end_i, end_j) = locate_char(data, 'E').expect("no end");
+
+    let heights = heights(data);
+
+    let max_i = heights.len();
+    let max_j = heights[0].len();
+
+    let dx = [0, 0, 1, -1];
+    let dy = [1, -1, 0, 0];
+
+    let distance_row = vec![u64::MAX; max_j];
+    let mut distances = vec![distance_row; max_i];
+
+    distances[end_i][end_j] = 0;
+
+    let mut queue = VecDeque::from([(end_i, end_j)]);
+    while let Some((i, j)) = queue.pop_front() {
+        let next_distance = distances[i][j] + 1;
+
+        for (dx, dy) in dx.iter().copied().zip(dy.iter().copied()) {
+            if let Some((next_i, next_j)) = offset_coords(i, j, dx, dy, max_i, max_j) {
+                if heights[i][j] <= heights[next_i][next_j] + 1
+                    && distances[next_i][next_j] == u64::MAX
+                {
+                    if heights[next_i][next_j] == 0 {
+                        return next_distance;
+                    }
+
+                    distances[next_i][next_j] = next_distance;
+                    queue.push_back((next_i, next_j));
+                }
+            }
+        }
+    }
+
+    unreachable!("no solution found")
+}
@@ -0,0 +1,259 @@
+use std::{cmp::Ordering, fmt};
+
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+use nom::character::complete::{char, digit1, space0};
+use nom::{IResult, Parser};
+use nom_supreme::{
+    error::{BaseErrorKind, ErrorTree},
+    multi::collect_separated_terminated,
+    parser_ext::ParserExt,
+};
+
+/// Packets nested deeper than this are rejected, instead of overflowing the stack
+/// while parsing or comparing them.
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// A packet, or a value in one. Elements are equal when they're in neither order, so a
+/// number is equal to a list of just that number, however deeply it's nested.
+#[derive(Debug, Clone)]
+pub enum Element {
+    Number(i64),
+    List(Vec<Element>),
+}
+
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Element {}
+
+impl PartialOrd for Element {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Element {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Element::Number(a), Element::Number(b)) => a.cmp(b),
+            (Element::Number(_), Element::List(_)) => Element::List(vec![self.clone()]).cmp(other),
+            (Element::List(_), Element::Number(_)) => self.cmp(&Element::List(vec![other.clone()])),
+            (Element::List(a), Element::List(b)) => a
+                .iter()
+                .zip(b.iter())
+                .map(|(l, r)| l.cmp(r))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+        }
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Element::Number(n) => write!(f, "{n}"),
+            Element::List(elements) => write!(f, "[{}]", elements.iter().join(",")),
+        }
+    }
+}
+
+fn number(input: &str) -> IResult<&str, i64, ErrorTree<&str>> {
+    digit1.parse_from_str().parse(input)
+}
+
+fn nested_list(depth: usize) -> impl FnMut(&str) -> IResult<&str, Element, ErrorTree<&str>> {
+    move |input| {
+        if depth > MAX_NESTING_DEPTH && input.starts_with('[') {
+            let expected = format!("a list nested at most {MAX_NESTING_DEPTH} levels deep");
+            return Err(nom::Err::Failure(ErrorTree::Base {
+                location: input,
+                kind: BaseErrorKind::External(expected.into()),
+            }));
+        }
+
+        let mut parser = collect_separated_terminated(
+            number
+                .terminated(space0)
+                .map(Element::Number)
+                .or(nested_list(depth + 1)),
+            char(',').terminated(space0),
+            char(']'),
+        )
+        .or(char(']').value(vec![]))
+        .preceded_by(char('[').terminated(space0));
+
+        parser
+            .parse(input)
+            .map(|(input, res)| (input, Element::List(res)))
+    }
+}
+
+impl Element {
+    pub fn parse(source: Source<'_>, input: &str) -> Result<Self, ParseError> {
+        match nested_list(1)(input) {
+            Ok(("", parsed)) => Ok(parsed),
+            Ok((remainder, _)) => Err(source.error_at(remainder, "end of line")),
+            Err(e) => Err(source.error_tree(e)),
+        }
+    }
+}
+
+#[aoc_parser(day = 13)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<(Element, Element)>, ParseError> {
+    source
+        .paragraphs()
+        .map(|x| {
+            let (first, second) = source.split_once(x, "\n")?;
+            Ok((
+                Element::parse(source, first)?,
+                Element::parse(source, second)?,
+            ))
+        })
+        .collect()
+}
+
+#[aoc(day = 13, part = 1)]
+pub fn solve_part1(data: &[(Element, Element)]) -> usize {
+    data.iter()
+        .enumerate()
+        .filter_map(|(idx, (left, right))| (left <= right).then_some(idx + 1))
+        .sum()
+}
+
+/// Where the dividers would go if the packets were sorted, without sorting them: after
+/// every packet less than them, and ahead of any that are equal to them.
+#[aoc(day = 13, part = 2)]
+pub fn solve_part2(data: &[(Element, Element)]) -> usize {
+    let divider_a = Element::List(vec![Element::List(vec![Element::Number(2)])]);
+    let divider_b = Element::List(vec![Element::List(vec![Element::Number(6)])]);
+
+    let packets = data.iter().flat_map(|(a, b)| [a, b]);
+    let idx_a = packets.clone().filter(|p| *p < &divider_a).count();
+    // The first divider is before the second one too.
+    let idx_b = packets.filter(|p| *p < &divider_b).count() + 1;
+
+    (idx_a + 1) * (idx_b + 1)
+}
+
+/// The packet with the given number, counting from 1 in the order of the input.
+fn packet<'a>(packets: &[&'a Element], args: &[&str], index: usize) -> Result<&'a Element, String> {
+    let number: usize = repl::arg(args, index, "a packet number")?;
+    number
+        .checked_sub(1)
+        .and_then(|idx| packets.get(idx).copied())
+        .ok_or_else(|| format!("there are only {} packets", packets.len()))
+}
+
+/// Commands for comparing packets, which are numbered from 1 in the order of the input.
+pub fn repl(data: &[(Element, Element)]) -> Repl<'_, Vec<&Element>> {
+    let packets: Vec<&Element> = data.iter().flat_map(|(a, b)| [a, b]).collect();
+    Repl::new(13, packets)
+        .command("show", "<packet>", "show a packet", |packets, args| {
+            Ok(packet(packets, args, 0)?.to_string())
+        })
+        .command(
+            "compare",
+            "<packet> <packet>",
+            "compare two packets",
+            |packets, args| {
+                let (a, b) = (packet(packets, args, 0)?, packet(packets, args, 1)?);
+                let ordering = match a.cmp(b) {
+                    Ordering::Less => "<",
+                    Ordering::Equal => "=",
+                    Ordering::Greater => ">",
+                };
+                Ok(format!("{a}\n{ordering} {b}"))
+            },
+        )
+        .command(
+            "pair",
+            "<pair>",
+            "check whether a pair is in the right order",
+            |_, args| {
+                let pair: usize = repl::arg(args, 0, "a pair number")?;
+                let (a, b) = pair
+                    .checked_sub(1)
+                    .and_then(|idx| data.get(idx))
+                    .ok_or_else(|| format!("there are only {} pairs", data.len()))?;
+                let order = if a <= b {
+                    "the right order"
+                } else {
+                    "the wrong order"
+                };
+                Ok(format!("pair {pair} is in {order}"))
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use proptest::prelude::*;
+
+    use super::Element;
+
+    fn element() -> impl Strategy<Value = Element> {
+        let leaf = (0i64..4).prop_map(Element::Number);
+        leaf.prop_recursive(4, 24, 4, |inner| {
+            prop::collection::vec(inner, 0..4).prop_map(Element::List)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn ordering_is_reflexive(a in element()) {
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+        }
+
+        #[test]
+        fn ordering_is_antisymmetric(a in element(), b in element()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        }
+
+        #[test]
+        fn ordering_is_transitive(a in element(), b in element(), c in element()) {
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+            if a >= b && b >= c {
+                prop_assert!(a >= c);
+            }
+        }
+
+        #[test]
+        fn equality_agrees_with_the_ordering(a in element(), b in element()) {
+            prop_assert_eq!(a == b, a.cmp(&b).is_eq());
+        }
+
+        #[test]
+        fn numbers_compare_like_single_element_lists(n in 0i64..4, b in element()) {
+            let wrapped = Element::List(vec![Element::Number(n)]);
+            prop_assert_eq!(Element::Number(n).cmp(&b), wrapped.cmp(&b));
+            prop_assert_eq!(Element::Number(n), wrapped);
+        }
+
+        #[test]
+        fn elements_that_compare_equal_do_not_decide_the_order(
+            n in 0i64..4,
+            a in prop::collection::vec(element(), 0..4),
+            b in prop::collection::vec(element(), 0..4),
+        ) {
+            let expected = Element::List(a.clone()).cmp(&Element::List(b.clone()));
+
+            let mut left = vec![Element::Number(n)];
+            left.extend(a);
+            let mut right = vec![Element::List(vec![Element::Number(n)])];
+            right.extend(b);
+            prop_assert_eq!(Element::List(left).cmp(&Element::List(right)), expected);
+        }
+    }
+}
@@ -0,0 +1,689 @@
+use std::{
+    cell::RefCell,
+    cmp::max,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use common::parse::{ParseError, Source};
+use common::repl::Repl;
+use common::timeline::{self, Simulation, Timeline};
+use common::visualize::Frames;
+use common::{aoc, aoc_parser};
+use geometry::Vector2D;
+#[allow(unused_imports)]
+use itertools::Itertools;
+use tracing::debug;
+
+pub mod geometry;
+
+#[aoc_parser(day = 14)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Vec<(i64, i64)>>, ParseError> {
+    source
+        .lines()
+        .map(|x| {
+            let mut path: Vec<(i64, i64)> = vec![];
+            for tuple in x.split(" -> ") {
+                let (a, b) = source.split_once(tuple, ",")?;
+
+                // flip the coordinates so the axes are (down, right)
+                let point = (
+                    source.parse_at(b, "a coordinate")?,
+                    source.parse_at(a, "a coordinate")?,
+                );
+                if let Some(previous) = path.last() {
+                    if previous.0 != point.0 && previous.1 != point.1 {
+                        return Err(source
+                            .error_at(tuple, "a point in a straight line from the previous one"));
+                    }
+                }
+                path.push(point);
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+pub fn build_cave(input_data: &[Vec<(i64, i64)>]) -> (BTreeSet<Line>, BTreeMap<i64, i64>) {
+    let lines: BTreeSet<_> = input_data
+        .iter()
+        .flat_map(|segment| {
+            segment
+                .iter()
+                .tuple_windows()
+                .map(|(pt_a, pt_b)| Line::new(*pt_a, *pt_b))
+        })
+        .collect();
+
+    let mut lowest_point_per_vertical = BTreeMap::new();
+    for line in &lines {
+        let start_pt = line.start.point;
+        let end_pt = line.end.point;
+
+        if line.is_horizontal {
+            assert_eq!(start_pt.x, end_pt.x);
+            assert!(start_pt.y <= end_pt.y);
+            for y in start_pt.y..=end_pt.y {
+                lowest_point_per_vertical
+                    .entry(y)
+                    .and_modify(|value| *value = max(*value, start_pt.x))
+                    .or_insert(start_pt.x);
+            }
+        } else {
+            assert_eq!(start_pt.y, end_pt.y);
+            assert!(start_pt.x <= end_pt.x);
+
+            lowest_point_per_vertical
+                .entry(start_pt.y)
+                .and_modify(|value| *value = max(*value, end_pt.x))
+                .or_insert(end_pt.x);
+        }
+    }
+
+    (lines, lowest_point_per_vertical)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineEndpoint {
+    point: Vector2D,
+    line_end: bool,
+}
+
+impl LineEndpoint {
+    fn new(x: i64, y: i64, line_end: bool) -> Self {
+        Self {
+            point: Vector2D::new(x, y),
+            line_end,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Line {
+    start: LineEndpoint,
+    end: LineEndpoint,
+    is_horizontal: bool,
+}
+
+impl Line {
+    fn new(pt_a: (i64, i64), pt_b: (i64, i64)) -> Self {
+        let (a_x, a_y) = pt_a;
+        let (b_x, b_y) = pt_b;
+
+        let is_horizontal = a_x == b_x;
+
+        let (start, end) = if a_x == b_x {
+            if a_y <= b_y {
+                let endpoint_a = LineEndpoint::new(a_x, a_y, false);
+                let endpoint_b = LineEndpoint::new(b_x, b_y, true);
+                (endpoint_a, endpoint_b)
+            } else {
+                let endpoint_a = LineEndpoint::new(a_x, a_y, true);
+                let endpoint_b = LineEndpoint::new(b_x, b_y, false);
+                (endpoint_b, endpoint_a)
+            }
+        } else if a_y == b_y {
+            if a_x <= b_x {
+                let endpoint_a = LineEndpoint::new(a_x, a_y, false);
+                let endpoint_b = LineEndpoint::new(b_x, b_y, true);
+                (endpoint_a, endpoint_b)
+            } else {
+                let endpoint_a = LineEndpoint::new(a_x, a_y, true);
+                let endpoint_b = LineEndpoint::new(b_x, b_y, false);
+                (endpoint_b, endpoint_a)
+            }
+        } else {
+            unreachable!("line is neither horizontal nor vertical: {pt_a:?}, {pt_b:?}")
+        };
+
+        assert!(!start.line_end);
+        assert!(end.line_end);
+        Self {
+            start,
+            end,
+            is_horizontal,
+        }
+    }
+}
+
+const SAND_ORIGIN: Vector2D = Vector2D::new(0, 500);
+
+pub struct LocalMap {
+    map: Vec<[bool; 1024]>,
+    origin: Vector2D,
+}
+
+impl LocalMap {
+    fn new(origin: Vector2D) -> Self {
+        Self {
+            map: vec![[false; 1024]; 1024],
+            origin,
+        }
+    }
+
+    fn new_with_centered_point(pt: Vector2D) -> Self {
+        let target_pt = Vector2D::new(0, 511);
+        Self::new(target_pt - pt)
+    }
+
+    fn get_pt(&self, pt: Vector2D) -> bool {
+        let new_pt = pt + self.origin;
+        self.map[new_pt.x as usize][new_pt.y as usize]
+    }
+
+    #[allow(dead_code)]
+    fn get(&self, x: i64, y: i64) -> bool {
+        self.get_pt(Vector2D::new(x, y))
+    }
+
+    fn get_pt_mut(&mut self, pt: Vector2D) -> &mut bool {
+        let new_pt = pt + self.origin;
+        self.map
+            .get_mut(new_pt.x as usize)
+            .unwrap()
+            .get_mut(new_pt.y as usize)
+            .unwrap()
+    }
+
+    #[allow(dead_code)]
+    fn get_mut(&mut self, x: i64, y: i64) -> &mut bool {
+        self.get_pt_mut(Vector2D::new(x, y))
+    }
+
+    fn contains(&self, pt: Vector2D) -> bool {
+        let new_pt = pt + self.origin;
+        let size = self.map.len() as i64;
+        (0..size).contains(&new_pt.x) && (0..size).contains(&new_pt.y)
+    }
+
+    /// Mark the point as blocked, if it's within the bounds of the map.
+    fn fill(&mut self, x: i64, y: i64) {
+        let pt = Vector2D::new(x, y);
+        if self.contains(pt) {
+            *self.get_pt_mut(pt) = true;
+        }
+    }
+
+    fn near_boundary(&self, pt: Vector2D) -> bool {
+        let new_pt = pt + self.origin;
+
+        // ignore being near the top boundary, since sand only falls down
+        new_pt.y < 2 || new_pt.x >= 1020 || new_pt.y >= 1020
+    }
+}
+
+const DROP_DIRECTIONS: [Vector2D; 3] = [
+    Vector2D::new(1, 0),  // down
+    Vector2D::new(1, -1), // down + left
+    Vector2D::new(1, 1),  // down + right
+];
+
+fn populate_map(
+    local_map: &mut LocalMap,
+    sand_points: &BTreeSet<Vector2D>,
+    lines: &BTreeSet<Line>,
+) {
+    for sand_pt in sand_points.iter() {
+        local_map.fill(sand_pt.x, sand_pt.y);
+    }
+
+    for line in lines {
+        let start_pt = line.start.point;
+        let end_pt = line.end.point;
+
+        if line.is_horizontal {
+            assert_eq!(start_pt.x, end_pt.x);
+            assert!(start_pt.y <= end_pt.y);
+            for y in start_pt.y..=end_pt.y {
+                local_map.fill(start_pt.x, y);
+            }
+        } else {
+            assert_eq!(start_pt.y, end_pt.y);
+            assert!(start_pt.x <= end_pt.x);
+            for x in start_pt.x..=end_pt.x {
+                local_map.fill(x, start_pt.y);
+            }
+        }
+    }
+}
+
+fn drop_sand(
+    local_map: &mut LocalMap,
+    sand_points: &mut BTreeSet<Vector2D>,
+    lines: &BTreeSet<Line>,
+    lowest_point_per_vertical: &BTreeMap<i64, i64>,
+    sand_source: Vector2D,
+    frames: &mut Frames,
+) -> Option<Vector2D> {
+    if local_map.near_boundary(sand_source) {
+        let mut new_local_map = LocalMap::new_with_centered_point(sand_source);
+        let local_map = &mut new_local_map;
+        populate_map(local_map, sand_points, lines);
+        drop_sand(
+            local_map,
+            sand_points,
+            lines,
+            lowest_point_per_vertical,
+            sand_source,
+            frames,
+        )
+    } else {
+        for offset in DROP_DIRECTIONS.iter() {
+            let next_point = sand_source + *offset;
+            if !local_map.get_pt(next_point) {
+                // Check if the sand is about to fall into infinity.
+                if let Some(lowest_coord) = lowest_point_per_vertical.get(&next_point.y) {
+                    if *lowest_coord < next_point.x {
+                        return Some(sand_source);
+                    }
+                } else {
+                    // There are no walls in this vertical column at all.
+                    // The sand is going to fall into infinity.
+                    return Some(sand_source);
+                }
+
+                // Drop the sand to the next point. If it ends up falling into infinity,
+                // stop dropping sand.
+                let final_sand_source = drop_sand(
+                    local_map,
+                    sand_points,
+                    lines,
+                    lowest_point_per_vertical,
+                    next_point,
+                    frames,
+                );
+                if final_sand_source.is_some() {
+                    return final_sand_source;
+                }
+            }
+        }
+
+        debug!(
+            ?sand_source,
+            resting = sand_points.len() + 1,
+            "sand came to rest"
+        );
+        sand_points.insert(sand_source);
+        *local_map.get_pt_mut(sand_source) = true;
+        frames.push(|| render_cave(lines, sand_points));
+        None
+    }
+}
+
+/// Every point that's covered by rock.
+fn rock_points(lines: &BTreeSet<Line>) -> BTreeSet<Vector2D> {
+    let mut rock: BTreeSet<Vector2D> = Default::default();
+    for line in lines {
+        let (start_pt, end_pt) = (line.start.point, line.end.point);
+        for x in start_pt.x..=end_pt.x {
+            for y in start_pt.y..=end_pt.y {
+                rock.insert(Vector2D::new(x, y));
+            }
+        }
+    }
+    rock
+}
+
+/// The floor two below the lowest rock, wide enough for the pile of sand that comes to rest
+/// on it.
+fn floor(lowest_point_per_vertical: &BTreeMap<i64, i64>) -> Line {
+    let floor_x = *lowest_point_per_vertical.values().max().unwrap() + 2;
+    Line::new(
+        (floor_x, SAND_ORIGIN.y - floor_x - 1),
+        (floor_x, SAND_ORIGIN.y + floor_x + 1),
+    )
+}
+
+/// Draw the rock and the sand that has come to rest, with the source of the sand at the top.
+fn render_cave(lines: &BTreeSet<Line>, sand_points: &BTreeSet<Vector2D>) -> String {
+    let rock = rock_points(lines);
+    let points = || rock.iter().chain(sand_points.iter()).chain([&SAND_ORIGIN]);
+    let (min_y, max_y) = points().map(|pt| pt.y).minmax().into_option().unwrap();
+    let max_x = points().map(|pt| pt.x).max().unwrap();
+
+    let mut result = String::new();
+    for x in 0..=max_x {
+        for y in min_y..=max_y {
+            let point = Vector2D::new(x, y);
+            result.push(if point == SAND_ORIGIN {
+                '+'
+            } else if rock.contains(&point) {
+                '#'
+            } else if sand_points.contains(&point) {
+                'o'
+            } else {
+                '.'
+            });
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[aoc(day = 14, part = 1)]
+pub fn solve_part1(paths: &[Vec<(i64, i64)>], frames: &mut Frames) -> usize {
+    let (lines, lowest_point_per_vertical) = build_cave(paths);
+    let mut sand_points: BTreeSet<Vector2D> = Default::default();
+
+    let mut local_map = LocalMap::new(Vector2D::new(0, 0));
+    populate_map(&mut local_map, &sand_points, &lines);
+    let stopping = drop_sand(
+        &mut local_map,
+        &mut sand_points,
+        &lines,
+        &lowest_point_per_vertical,
+        SAND_ORIGIN,
+        frames,
+    );
+    assert!(stopping.is_some());
+    frames.finish(|| render_cave(&lines, &sand_points));
+
+    sand_points.len()
+}
+
+#[aoc(day = 14, part = 2)]
+pub fn solve_part2(paths: &[Vec<(i64, i64)>], frames: &mut Frames) -> usize {
+    let (mut lines, mut lowest_point_per_vertical) = build_cave(paths);
+    let floor = floor(&lowest_point_per_vertical);
+    let (floor_start, floor_end) = (floor.start.point, floor.end.point);
+    for y in floor_start.y..=floor_end.y {
+        lowest_point_per_vertical.insert(y, floor_start.x);
+    }
+    lines.insert(floor);
+
+    let mut sand_points: BTreeSet<Vector2D> = Default::default();
+
+    let mut local_map = LocalMap::new(Vector2D::new(0, 0));
+    populate_map(&mut local_map, &sand_points, &lines);
+    let stopping = drop_sand(
+        &mut local_map,
+        &mut sand_points,
+        &lines,
+        &lowest_point_per_vertical,
+        SAND_ORIGIN,
+        frames,
+    );
+    assert!(stopping.is_none());
+    assert!(local_map.get_pt(SAND_ORIGIN));
+    frames.finish(|| render_cave(&lines, &sand_points));
+
+    sand_points.len()
+}
+
+/// The cave filling up with sand one grain at a time, for stepping through in the REPL. It
+/// follows each grain all the way down rather than taking the solutions' shortcuts, which is
+/// slow but plenty fast enough for looking at a step at a time.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    lines: Rc<BTreeSet<Line>>,
+    rock: Rc<BTreeSet<Vector2D>>,
+    sand: Rc<RefCell<SandLog>>,
+
+    /// How many of the grains in the log have come to rest by this step.
+    resting: usize,
+
+    /// The depth of the lowest rock, past which sand falls forever.
+    lowest: i64,
+}
+
+impl Cave {
+    pub fn new(paths: &[Vec<(i64, i64)>], has_floor: bool) -> Self {
+        let (mut lines, lowest_point_per_vertical) = build_cave(paths);
+        if has_floor {
+            lines.insert(floor(&lowest_point_per_vertical));
+        }
+        let rock = rock_points(&lines);
+        Self {
+            lowest: rock.iter().map(|pt| pt.x).max().unwrap(),
+            lines: Rc::new(lines),
+            rock: Rc::new(rock),
+            sand: Default::default(),
+            resting: 0,
+        }
+    }
+
+    /// How many grains of sand have come to rest.
+    pub fn resting_sand(&self) -> usize {
+        self.resting
+    }
+
+    fn is_blocked(&self, pt: Vector2D) -> bool {
+        let is_sand = |sand: &SandLog| sand.order.get(&pt).is_some_and(|&idx| idx < self.resting);
+        self.rock.contains(&pt) || is_sand(&self.sand.borrow())
+    }
+}
+
+/// Every grain of sand that has come to rest in a cave, in the order they did, shared by the
+/// cave and its copies. The sand only ever piles up, the same way every time, so a copy from
+/// an earlier step is the first few grains of the log, and the timeline's checkpoints don't
+/// each need a copy of all the sand before them.
+#[derive(Debug, Default)]
+struct SandLog {
+    grains: Vec<Vector2D>,
+
+    /// Where each grain is in `grains`.
+    order: BTreeMap<Vector2D, usize>,
+}
+
+impl Simulation for Cave {
+    /// Drop a grain of sand, which finishes the simulation if it falls into the abyss or if
+    /// the source is already blocked.
+    fn step(&mut self) -> bool {
+        let mut grain = SAND_ORIGIN;
+        if self.is_blocked(grain) {
+            return false;
+        }
+        while grain.x <= self.lowest {
+            let next = DROP_DIRECTIONS
+                .iter()
+                .map(|offset| grain + *offset)
+                .find(|&pt| !self.is_blocked(pt));
+            match next {
+                Some(next) => grain = next,
+                None => {
+                    let mut sand = self.sand.borrow_mut();
+                    if self.resting == sand.grains.len() {
+                        sand.order.insert(grain, self.resting);
+                        sand.grains.push(grain);
+                    }
+                    debug_assert_eq!(sand.grains[self.resting], grain);
+                    self.resting += 1;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn render(&self) -> String {
+        let sand = self.sand.borrow();
+        let sand_points = sand.grains[..self.resting].iter().copied().collect();
+        render_cave(&self.lines, &sand_points)
+    }
+}
+
+/// Commands for dropping the sand a grain at a time, into the abyss until the floor is put in.
+pub fn repl(paths: &[Vec<(i64, i64)>]) -> Repl<'static, Timeline<Cave>> {
+    let paths = paths.to_vec();
+    timeline::repl(14, Cave::new(&paths, false)).command(
+        "floor",
+        "<on|off>",
+        "start again with or without the floor",
+        move |timeline, args| {
+            let has_floor = match args.first().copied() {
+                Some("on") => true,
+                Some("off") => false,
+                _ => return Err("expected on or off".to_string()),
+            };
+            *timeline = Timeline::new(Cave::new(&paths, has_floor));
+            Ok(format!("started again with the floor {}", args[0]))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use common::parse::Source;
+    use common::visualize::Frames;
+
+    use super::{parse_input, solve_part1, solve_part2, Cave, Simulation, Timeline};
+
+    /// Simulate the sand one grain and one step at a time, with a floor if one is given.
+    ///
+    /// Without a floor, returns `None` if the sand source gets blocked before any sand
+    /// falls into the abyss, since the solution assumes that never happens.
+    fn reference_resting_sand(paths: &[Vec<(i64, i64)>], has_floor: bool) -> Option<usize> {
+        let mut blocked = HashSet::new();
+        for path in paths {
+            for (start, end) in path.iter().zip(path.iter().skip(1)) {
+                for x in start.0.min(end.0)..=start.0.max(end.0) {
+                    for y in start.1.min(end.1)..=start.1.max(end.1) {
+                        blocked.insert((x, y));
+                    }
+                }
+            }
+        }
+        let lowest = blocked.iter().map(|(x, _)| *x).max().unwrap();
+        let floor = lowest + 2;
+
+        let mut resting = 0;
+        while !blocked.contains(&(0, 500)) {
+            let mut sand = (0, 500);
+            loop {
+                let next = [(1, 0), (1, -1), (1, 1)]
+                    .into_iter()
+                    .map(|(dx, dy)| (sand.0 + dx, sand.1 + dy))
+                    .find(|pt| !blocked.contains(pt) && (!has_floor || pt.0 < floor));
+                match next {
+                    Some(pt) => sand = pt,
+                    None => break,
+                }
+                if !has_floor && sand.0 > lowest {
+                    return Some(resting);
+                }
+            }
+            blocked.insert(sand);
+            resting += 1;
+        }
+        has_floor.then_some(resting)
+    }
+
+    /// A rock path made of straight segments, starting near the sand origin.
+    fn path() -> impl Strategy<Value = Vec<(i64, i64)>> {
+        let step = (any::<bool>(), -6i64..=6);
+        ((2i64..20, 490i64..510), prop::collection::vec(step, 1..4)).prop_map(|(start, steps)| {
+            let mut path = vec![start];
+            for (vertical, delta) in steps {
+                let (x, y) = *path.last().unwrap();
+                let next = if vertical {
+                    ((x + delta).max(1), y)
+                } else {
+                    (x, y + delta)
+                };
+                path.push(next);
+            }
+            path
+        })
+    }
+
+    fn paths() -> impl Strategy<Value = Vec<Vec<(i64, i64)>>> {
+        prop::collection::vec(path(), 1..5)
+    }
+
+    /// Rocks deep enough that the falling sand leaves the initial `LocalMap`.
+    fn deep_paths() -> impl Strategy<Value = Vec<Vec<(i64, i64)>>> {
+        paths().prop_map(|paths| {
+            paths
+                .into_iter()
+                .map(|path| path.into_iter().map(|(x, y)| (x + 1010, y)).collect())
+                .collect()
+        })
+    }
+
+    /// The cave once the sand stops, without and with the floor.
+    #[test]
+    fn renders_the_example_cave() {
+        let paths =
+            parse_input(Source::new("example.txt", include_str!("../example.txt"))).unwrap();
+        let mut frames = Frames::final_only();
+        solve_part1(&paths, &mut frames);
+        insta::assert_snapshot!("abyss", frames.frames().last().unwrap());
+
+        let mut frames = Frames::final_only();
+        solve_part2(&paths, &mut frames);
+        insta::assert_snapshot!("floor", frames.frames().last().unwrap());
+    }
+
+    /// Dropping the sand a grain at a time ends up the same as the solutions.
+    #[test]
+    fn cave_fills_like_the_solutions() {
+        let paths =
+            parse_input(Source::new("example.txt", include_str!("../example.txt"))).unwrap();
+        for (has_floor, resting) in [(false, 24), (true, 93)] {
+            let mut cave = Cave::new(&paths, has_floor);
+            while cave.step() {}
+            assert_eq!(cave.resting_sand(), resting);
+
+            let mut frames = Frames::final_only();
+            if has_floor {
+                solve_part2(&paths, &mut frames);
+            } else {
+                solve_part1(&paths, &mut frames);
+            }
+            assert_eq!(&cave.render(), frames.frames().last().unwrap());
+        }
+    }
+
+    /// Going back to a checkpoint shares the sand with it, and settles the same grains again.
+    #[test]
+    fn timeline_shares_the_sand_between_checkpoints() {
+        let paths =
+            parse_input(Source::new("example.txt", include_str!("../example.txt"))).unwrap();
+        let mut timeline = Timeline::new(Cave::new(&paths, true));
+        timeline.goto(93);
+        let end = timeline.state().render();
+
+        timeline.goto(43);
+        let mut cave = Cave::new(&paths, true);
+        for _ in 0..43 {
+            cave.step();
+        }
+        assert_eq!(timeline.state().render(), cave.render());
+        assert_eq!(timeline.state().resting_sand(), 43);
+        assert_eq!(timeline.state().sand.borrow().grains.len(), 93);
+
+        timeline.goto(93);
+        assert_eq!(timeline.state().render(), end);
+        assert_eq!(timeline.state().sand.borrow().grains.len(), 93);
+    }
+
+    proptest! {
+        #[test]
+        fn part1_matches_step_by_step_simulation(paths in paths()) {
+            let expected = reference_resting_sand(&paths, false);
+            prop_assume!(expected.is_some());
+
+            prop_assert_eq!(Some(solve_part1(&paths, &mut Frames::disabled())), expected);
+        }
+
+        #[test]
+        fn part1_matches_step_by_step_simulation_after_recentering(paths in deep_paths()) {
+            let expected = reference_resting_sand(&paths, false);
+            prop_assume!(expected.is_some());
+
+            prop_assert_eq!(Some(solve_part1(&paths, &mut Frames::disabled())), expected);
+        }
+
+        #[test]
+        fn part2_matches_step_by_step_simulation(paths in paths()) {
+            prop_assert_eq!(
+                Some(solve_part2(&paths, &mut Frames::disabled())),
+                reference_resting_sand(&paths, true)
+            );
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use std::{collections::BTreeSet, sync::Mutex};
+
+use rayon::prelude::*;
+
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::{aoc, aoc_parser};
+use geometry::Vector2D;
+
+pub mod geometry;
+
+fn parse_point(source: Source<'_>, point: &str) -> Result<Vector2D, ParseError> {
+    let (x, y) = source.split_once(source.strip_prefix(point, "x=")?, ", y=")?;
+    let x = source.parse_at(x, "an x coordinate")?;
+    let y = source.parse_at(y, "a y coordinate")?;
+    Ok(Vector2D::new(x, y))
+}
+
+fn parse_line(source: Source<'_>, line: &str) -> Result<(Vector2D, Vector2D), ParseError> {
+    let line = source.strip_prefix(line, "Sensor at ")?.trim_end();
+    let (sensor, beacon) = source.split_once(line, ": closest beacon is at ")?;
+    Ok((parse_point(source, sensor)?, parse_point(source, beacon)?))
+}
+
+fn cone_at_y(origin: Vector2D, y: i64, radius: i64) -> Option<(i64, i64)> {
+    assert!(radius >= 0);
+    let distance = (origin.y - y).abs();
+    let leftover_distance = radius - distance;
+    if leftover_distance < 0 {
+        None
+    } else {
+        Some((origin.x - leftover_distance, origin.x + leftover_distance))
+    }
+}
+
+/// Events in the part 1 sweep. At the same x coordinate, events are processed in this order,
+/// so that a beacon at the very edge of a sensor's coverage still counts as covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SweepEvent {
+    CoverageStart,
+    Beacon,
+    CoverageEnd,
+}
+
+#[aoc_parser(day = 15)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<(Vector2D, Vector2D)>, ParseError> {
+    source
+        .lines()
+        .map(|line| parse_line(source, line))
+        .collect()
+}
+
+#[aoc(day = 15, part = 1, params(y = 2000000))]
+pub fn solve_part1(data: &[(Vector2D, Vector2D)], target_y: i64) -> i64 {
+    let mut endpoints = Vec::new();
+    for (sensor, beacon) in data {
+        let radius = (*sensor - *beacon).manhattan_length();
+        if let Some((start_x, end_x)) = cone_at_y(*sensor, target_y, radius) {
+            endpoints.push((start_x, SweepEvent::CoverageStart));
+            endpoints.push((end_x, SweepEvent::CoverageEnd));
+        }
+    }
+
+    // Beacons on the target y coordinate are relevant, add them to the sweep.
+    // Several sensors may share the same closest beacon, so only add each one once.
+    let beacons_on_target: BTreeSet<_> = data
+        .iter()
+        .filter_map(|(_, beacon)| (beacon.y == target_y).then_some(beacon.x))
+        .collect();
+    endpoints.extend(
+        beacons_on_target
+            .into_iter()
+            .map(|x| (x, SweepEvent::Beacon)),
+    );
+
+    let mut coverage_depth = 0;
+    let mut denied_locations = 0;
+    let mut coverage_began = None;
+
+    endpoints.sort_unstable();
+
+    // Sweep across the relevant coordinates.
+    for (x_coord, event) in endpoints {
+        match event {
+            SweepEvent::Beacon => {
+                // Found a beacon on this target y coordinate that's otherwise covered.
+                if coverage_depth > 0 {
+                    denied_locations -= 1;
+                }
+            }
+            SweepEvent::CoverageStart => {
+                if coverage_depth == 0 {
+                    coverage_began = Some(x_coord);
+                }
+                coverage_depth += 1;
+            }
+            SweepEvent::CoverageEnd => {
+                coverage_depth -= 1;
+                if coverage_depth == 0 {
+                    let began = coverage_began.unwrap();
+                    denied_locations += x_coord + 1 - began;
+                }
+            }
+        }
+    }
+
+    assert!(denied_locations >= 0);
+    denied_locations
+}
+
+fn allowed_location(
+    data: &[(Vector2D, Vector2D)],
+    beacons: &BTreeSet<Vector2D>,
+    target_y: i64,
+    cutoff_coord: i64,
+) -> Option<Vector2D> {
+    let mut endpoints = Vec::new();
+    for (sensor, beacon) in data {
+        let radius = (*sensor - *beacon).manhattan_length();
+        if let Some((start_x, end_x)) = cone_at_y(*sensor, target_y, radius) {
+            endpoints.push((start_x, false));
+            endpoints.push((end_x, true));
+        }
+    }
+
+    let mut coverage_depth = 0;
+    let mut coverage_ended = Some(-1);
+
+    endpoints.sort_unstable();
+
+    // Sweep across the relevant coordinates.
+    for (x_coord, is_end) in endpoints.iter().copied() {
+        if x_coord > cutoff_coord && coverage_depth > 0 {
+            break;
+        }
+
+        if let Some(ended_x) = coverage_ended {
+            assert!(!is_end);
+
+            let gap_end = std::cmp::min(x_coord, cutoff_coord + 1);
+            for candidate_x in std::cmp::max(0, ended_x + 1)..gap_end {
+                let candidate = Vector2D::new(candidate_x, target_y);
+                if !beacons.contains(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        if !is_end {
+            coverage_depth += 1;
+        } else {
+            coverage_depth -= 1;
+        }
+
+        if coverage_depth == 0 {
+            coverage_ended = Some(x_coord);
+        } else {
+            coverage_ended = None;
+        }
+    }
+
+    if let Some(ended_x) = coverage_ended {
+        for candidate_x in std::cmp::max(0, ended_x + 1)..=cutoff_coord {
+            let candidate = Vector2D::new(candidate_x, target_y);
+            if !beacons.contains(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+#[aoc(day = 15, part = 2, params(cutoff = 4000000))]
+pub fn solve_part2(data: &[(Vector2D, Vector2D)], cutoff_coord: i64) -> i64 {
+    let beacons = data.iter().map(|(_, beacon)| *beacon).collect();
+
+    let mut answer: Mutex<Option<Vector2D>> = None.into();
+
+    (0..=cutoff_coord).into_par_iter().for_each(|target_y| {
+        if let Some(allowed) = allowed_location(data, &beacons, target_y, cutoff_coord) {
+            let mut guard = answer.lock().unwrap();
+            assert_eq!(*guard, None);
+            *guard = Some(allowed);
+        }
+    });
+
+    let allowed = answer
+        .get_mut()
+        .expect("failed to get mutex")
+        .expect("no solution found");
+    allowed.x * 4000000 + allowed.y
+}
+
+fn point_arg(args: &[&str]) -> Result<Vector2D, String> {
+    let x = repl::arg(args, 0, "an x coordinate")?;
+    let y = repl::arg(args, 1, "a y coordinate")?;
+    Ok(Vector2D::new(x, y))
+}
+
+/// Commands for checking which sensors cover a point or a row.
+pub fn repl(data: &[(Vector2D, Vector2D)]) -> Repl<'_, ()> {
+    Repl::new(15, ())
+        .command(
+            "at",
+            "<x> <y>",
+            "list the sensors that cover a point",
+            move |_, args| {
+                let point = point_arg(args)?;
+                let mut lines = vec![];
+                if data.iter().any(|(_, beacon)| *beacon == point) {
+                    lines.push("a beacon is here".to_string());
+                }
+                for (sensor, beacon) in data {
+                    let radius = (*sensor - *beacon).manhattan_length();
+                    let distance = (point - *sensor).manhattan_length();
+                    if distance <= radius {
+                        lines.push(format!(
+                            "covered by the sensor at x={}, y={}, {distance} away, within its range of {radius}",
+                            sensor.x, sensor.y
+                        ));
+                    }
+                }
+                if lines.is_empty() {
+                    lines.push("not covered by any sensor".to_string());
+                }
+                Ok(lines.join("\n"))
+            },
+        )
+        .command(
+            "row",
+            "<y>",
+            "list the ranges of x that the sensors cover on a row",
+            move |_, args| {
+                let y = repl::arg(args, 0, "a y coordinate")?;
+                let mut cones: Vec<_> = data
+                    .iter()
+                    .filter_map(|(sensor, beacon)| {
+                        cone_at_y(*sensor, y, (*sensor - *beacon).manhattan_length())
+                    })
+                    .collect();
+                cones.sort_unstable();
+
+                // Merge the overlapping and adjacent ranges.
+                let mut ranges: Vec<(i64, i64)> = vec![];
+                for (start, end) in cones {
+                    match ranges.last_mut() {
+                        Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+                        _ => ranges.push((start, end)),
+                    }
+                }
+                if ranges.is_empty() {
+                    return Ok("no sensor covers this row".to_string());
+                }
+                Ok(ranges
+                    .iter()
+                    .map(|(start, end)| format!("{start}..={end}"))
+                    .join(", "))
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::{allowed_location, solve_part1, Vector2D};
+
+    fn covered(data: &[(Vector2D, Vector2D)], point: Vector2D) -> bool {
+        data.iter().any(|(sensor, beacon)| {
+            (point - *sensor).manhattan_length() <= (*beacon - *sensor).manhattan_length()
+        })
+    }
+
+    /// Count the denied locations on the row one cell at a time.
+    fn reference_denied_locations(data: &[(Vector2D, Vector2D)], target_y: i64) -> i64 {
+        let beacons: BTreeSet<_> = data.iter().map(|(_, beacon)| *beacon).collect();
+        let reach =
+            |(sensor, beacon): &(Vector2D, Vector2D)| (*beacon - *sensor).manhattan_length();
+        let min_x = data
+            .iter()
+            .map(|pair| pair.0.x - reach(pair))
+            .min()
+            .unwrap();
+        let max_x = data
+            .iter()
+            .map(|pair| pair.0.x + reach(pair))
+            .max()
+            .unwrap();
+
+        (min_x..=max_x)
+            .map(|x| Vector2D::new(x, target_y))
+            .filter(|point| covered(data, *point) && !beacons.contains(point))
+            .count() as i64
+    }
+
+    /// Find the leftmost uncovered location on the row one cell at a time.
+    fn reference_allowed_location(
+        data: &[(Vector2D, Vector2D)],
+        target_y: i64,
+        cutoff_coord: i64,
+    ) -> Option<Vector2D> {
+        (0..=cutoff_coord)
+            .map(|x| Vector2D::new(x, target_y))
+            .find(|point| !covered(data, *point))
+    }
+
+    fn point() -> impl Strategy<Value = Vector2D> {
+        (-10i64..30, -10i64..30).prop_map(|(x, y)| Vector2D::new(x, y))
+    }
+
+    fn sensors() -> impl Strategy<Value = Vec<(Vector2D, Vector2D)>> {
+        prop::collection::vec((point(), point()), 1..6)
+    }
+
+    proptest! {
+        #[test]
+        fn part1_sweep_matches_per_cell_check(data in sensors(), target_y in -15i64..35) {
+            prop_assert_eq!(
+                solve_part1(&data, target_y),
+                reference_denied_locations(&data, target_y)
+            );
+        }
+
+        #[test]
+        fn allowed_location_sweep_matches_per_cell_check(
+            data in sensors(),
+            target_y in 0i64..25,
+            cutoff_coord in 0i64..25,
+        ) {
+            let beacons = data.iter().map(|(_, beacon)| *beacon).collect();
+            prop_assert_eq!(
+                allowed_location(&data, &beacons, target_y, cutoff_coord),
+                reference_allowed_location(&data, target_y, cutoff_coord)
+            );
+        }
+    }
+}
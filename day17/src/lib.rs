@@ -0,0 +1,495 @@
+use std::collections::VecDeque;
+
+use common::cycle;
+use common::parse::{ParseError, Source};
+use common::repl::Repl;
+use common::timeline::{self, Simulation, Timeline};
+use common::visualize::Frames;
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+use tracing::{debug, trace};
+
+#[aoc_parser(day = 17)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Move>, ParseError> {
+    let text = source.text().trim_end();
+    if text.is_empty() {
+        let end = &source.text()[source.text().len()..];
+        return Err(source.error_at(end, "a jet pattern"));
+    }
+    text.char_indices()
+        .map(|(idx, c)| match c {
+            '<' => Ok(Move::Left),
+            '>' => Ok(Move::Right),
+            _ => Err(source.error_at(&text[idx..idx + c.len_utf8()], "`<` or `>`")),
+        })
+        .collect()
+}
+
+const CHAMBER_WIDTH: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Piece {
+    Row,
+    Plus,
+    Angle,
+    Column,
+    Square,
+}
+
+impl Piece {
+    #[rustfmt::skip]
+    const SHAPES: [[[bool; 4]; 4]; 5] = [
+        // Vertically flipped, so that the [0] row of each shape is down.
+        [
+            // Row
+            [true; 4],
+            [false; 4],
+            [false; 4],
+            [false; 4],
+        ],
+        [
+            // Plus
+            [false, true, false, false],
+            [true,  true,  true, false],
+            [false, true, false, false],
+            [false; 4],
+        ],
+        [
+            // Angle
+            [true,  true,  true, false],
+            [false, false, true, false],
+            [false, false, true, false],
+            [false; 4],
+        ],
+        [
+            // Column
+            [true, false, false, false],
+            [true, false, false, false],
+            [true, false, false, false],
+            [true, false, false, false],
+        ],
+        [
+            // Square
+            [true, true, false, false],
+            [true, true, false, false],
+            [false; 4],
+            [false; 4],
+        ],
+    ];
+
+    const ALL_PIECES: [Piece; 5] = [
+        Piece::Row,
+        Piece::Plus,
+        Piece::Angle,
+        Piece::Column,
+        Piece::Square,
+    ];
+
+    const fn shape(&self) -> [[bool; 4]; 4] {
+        let idx = match self {
+            Piece::Row => 0,
+            Piece::Plus => 1,
+            Piece::Angle => 2,
+            Piece::Column => 3,
+            Piece::Square => 4,
+        };
+
+        Self::SHAPES[idx]
+    }
+
+    const fn width(&self) -> usize {
+        match self {
+            Piece::Row => 4,
+            Piece::Plus => 3,
+            Piece::Angle => 3,
+            Piece::Column => 1,
+            Piece::Square => 2,
+        }
+    }
+
+    const fn height(&self) -> usize {
+        match self {
+            Piece::Row => 1,
+            Piece::Plus => 3,
+            Piece::Angle => 3,
+            Piece::Column => 4,
+            Piece::Square => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    occupied: VecDeque<[bool; CHAMBER_WIDTH]>,
+    spawn_height: usize,
+    spawn_column: usize,
+    truncated_rows: usize,
+}
+
+impl Board {
+    const EMPTY_ROW: [bool; CHAMBER_WIDTH] = [false; CHAMBER_WIDTH];
+    const FULL_ROW: [bool; CHAMBER_WIDTH] = [true; CHAMBER_WIDTH];
+    /// How far down from the top of the tower [`Board::open_space`] looks.
+    const OPEN_SPACE_DEPTH: usize = 64;
+
+    fn new(spawn_height: usize, spawn_column: usize) -> Self {
+        Self {
+            occupied: VecDeque::new(),
+            spawn_height,
+            spawn_column,
+            truncated_rows: 0,
+        }
+    }
+
+    fn check_walls_for_move(mv: Move, piece: Piece, column: usize) -> usize {
+        match mv {
+            Move::Left => column.saturating_sub(1),
+            Move::Right => {
+                if column + piece.width() < CHAMBER_WIDTH {
+                    column + 1
+                } else {
+                    column
+                }
+            }
+        }
+    }
+
+    fn show_tower(&self) -> String {
+        let mut result = String::with_capacity((CHAMBER_WIDTH + 1) * self.occupied.len() + 1);
+        for row in self.occupied.iter().rev() {
+            for slot in row {
+                if *slot {
+                    result += "#";
+                } else {
+                    result += ".";
+                }
+            }
+            result += "\n";
+        }
+        result
+    }
+
+    fn height(&self) -> usize {
+        self.occupied.len() + self.truncated_rows
+    }
+
+    /// The empty tiles that can be reached from above the tower by moving down and sideways,
+    /// one row per byte from the top down. Pieces can never get anywhere else, so two towers
+    /// with the same open space behave the same from then on, however tall they are.
+    ///
+    /// A gap that the jets never push a piece into would reach all the way down and never
+    /// repeat, so the search stops [`Board::OPEN_SPACE_DEPTH`] rows below the top: nothing
+    /// falling into a gap that deep lands anywhere near the top of the tower.
+    fn open_space(&self) -> Vec<u8> {
+        let top = self.occupied.len();
+        let bottom = top.saturating_sub(Self::OPEN_SPACE_DEPTH);
+        let mut open = vec![0u8; top];
+        let mut stack = (0..CHAMBER_WIDTH).map(|column| (top, column)).collect_vec();
+        while let Some((row, column)) = stack.pop() {
+            let mut neighbours = vec![];
+            if row > bottom {
+                neighbours.push((row - 1, column));
+            }
+            if row < top {
+                if column > 0 {
+                    neighbours.push((row, column - 1));
+                }
+                if column + 1 < CHAMBER_WIDTH {
+                    neighbours.push((row, column + 1));
+                }
+            }
+            for (row, column) in neighbours {
+                let bit = 1 << column;
+                if !self.occupied[row][column] && open[row] & bit == 0 {
+                    open[row] |= bit;
+                    stack.push((row, column));
+                }
+            }
+        }
+
+        open.into_iter().rev().take_while(|row| *row != 0).collect()
+    }
+
+    fn get_tile(&self, position: (usize, usize)) -> bool {
+        let (row, column) = position;
+        self.occupied[row - self.truncated_rows][column]
+    }
+
+    fn get_tile_mut(&mut self, position: (usize, usize)) -> &mut bool {
+        let (row, column) = position;
+        self.occupied
+            .get_mut(row - self.truncated_rows)
+            .unwrap()
+            .get_mut(column)
+            .unwrap()
+    }
+
+    fn get_row(&mut self, row: usize) -> &[bool; CHAMBER_WIDTH] {
+        self.occupied.get(row - self.truncated_rows).unwrap()
+    }
+
+    fn does_piece_fit(&self, piece: Piece, position: (usize, usize)) -> bool {
+        let (row, column) = position;
+
+        for i in 0..piece.height() {
+            for j in 0..piece.width() {
+                if piece.shape()[i][j] && self.get_tile((row + i, column + j)) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn drop_piece(&mut self, piece: Piece, moves_iter: &mut impl Iterator<Item = (usize, Move)>) {
+        let mut column = self.spawn_column;
+
+        // Compute the starting moves that happen before the piece reaches the tower.
+        let starting_moves = moves_iter
+            .by_ref()
+            .take(self.spawn_height + 1)
+            .collect_vec();
+        debug!(
+            ?piece,
+            jet = starting_moves.first().unwrap().0,
+            height = self.height(),
+            "dropping piece"
+        );
+        trace!(tower = %self.show_tower());
+
+        for (_, mv) in starting_moves {
+            column = Self::check_walls_for_move(mv, piece, column);
+        }
+        let mut row = self.occupied.len() + self.truncated_rows;
+
+        // Add rows to the board to fit the new piece.
+        self.occupied
+            .extend([Self::EMPTY_ROW].repeat(piece.height()));
+        assert!(
+            self.does_piece_fit(piece, (row, column)),
+            "piece didn't initially fit: {piece:?} {:?}\n{}",
+            (row, column),
+            self.show_tower()
+        );
+
+        loop {
+            let next_row = if row > self.truncated_rows {
+                row - 1
+            } else {
+                row
+            };
+            if next_row == row || !self.does_piece_fit(piece, (next_row, column)) {
+                // The piece can't drop down any more, and comes to a rest.
+                break;
+            }
+            row = next_row;
+
+            let next_column =
+                Self::check_walls_for_move(moves_iter.next().unwrap().1, piece, column);
+            if self.does_piece_fit(piece, (row, next_column)) {
+                // The piece can move sideways to the new position.
+                column = next_column;
+            }
+        }
+
+        let mut truncate_from_row = None;
+
+        // Add the new piece at its resting location.
+        for i in 0..piece.height() {
+            for j in 0..piece.width() {
+                if piece.shape()[i][j] {
+                    *self.get_tile_mut((row + i, column + j)) = true;
+                    if self.get_row(row + i) == &Self::FULL_ROW {
+                        truncate_from_row = Some(row + i);
+                    }
+                }
+            }
+        }
+
+        // Truncate any rows that are now unreachable due to being full or below a full row.
+        if let Some(truncated_row) = truncate_from_row {
+            for _ in 0..=(truncated_row - self.truncated_rows) {
+                self.occupied.pop_front().unwrap();
+            }
+            self.truncated_rows = truncated_row + 1; // because rows are zero-indexed
+        }
+
+        // Remove completely-empty rows from the top of the tower.
+        while self.occupied.back().unwrap() == &Self::EMPTY_ROW {
+            self.occupied.pop_back().unwrap();
+        }
+    }
+}
+
+#[aoc(day = 17, part = 1, params(pieces = 2022))]
+pub fn solve_part1(moves: &[Move], pieces: usize, frames: &mut Frames) -> usize {
+    let spawn_height = 3;
+    let spawn_column = 2;
+
+    let mut board = Board::new(spawn_height, spawn_column);
+    let mut move_cycle = moves.iter().copied().enumerate().cycle();
+    let piece_cycle = Piece::ALL_PIECES.iter().copied().cycle();
+
+    for piece in piece_cycle.take(pieces) {
+        board.drop_piece(piece, &mut move_cycle);
+        frames.push(|| board.show_tower());
+    }
+    frames.finish(|| board.show_tower());
+
+    board.height()
+}
+
+/// The state of the chamber between pieces: the tower, which piece falls next and which
+/// jet of gas pushes it first.
+#[derive(Debug, Clone)]
+pub struct Chamber<'a> {
+    board: Board,
+    moves: &'a [Move],
+    pieces: usize,
+    jet: usize,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(moves: &'a [Move]) -> Self {
+        let spawn_height = 3;
+        let spawn_column = 2;
+
+        Self {
+            board: Board::new(spawn_height, spawn_column),
+            moves,
+            pieces: 0,
+            jet: 0,
+        }
+    }
+
+    fn drop_next_piece(&mut self) {
+        let piece = Piece::ALL_PIECES[self.pieces % Piece::ALL_PIECES.len()];
+        let moves = self.moves;
+        let mut jets = (self.jet..).map(|i| (i % moves.len(), moves[i % moves.len()]));
+        self.board.drop_piece(piece, &mut jets);
+        self.pieces += 1;
+        self.jet = jets.next().unwrap().0;
+    }
+
+    /// Everything that decides how the following pieces fall: the next piece and jet, and
+    /// the open space at the top of the tower.
+    fn fingerprint(&self) -> (usize, usize, Vec<u8>) {
+        (
+            self.pieces % Piece::ALL_PIECES.len(),
+            self.jet,
+            self.board.open_space(),
+        )
+    }
+}
+
+/// Drop far too many pieces to simulate, by finding where the pieces and jets start falling
+/// the same way over and over, and extrapolating the height of the tower from there.
+#[aoc(day = 17, part = 2, params(pieces = 1_000_000_000_000))]
+pub fn solve_part2(moves: &[Move], pieces: usize, frames: &mut Frames) -> usize {
+    let cycle = cycle::find_cycle(
+        Chamber::new(moves),
+        usize::MAX,
+        |chamber| {
+            chamber.drop_next_piece();
+            frames.push(|| chamber.board.show_tower());
+        },
+        Chamber::fingerprint,
+        |chamber| chamber.board.height(),
+    )
+    .expect("the tower never repeated itself");
+
+    if frames.is_recording() {
+        // The tower only ever differs by a whole number of cycles from one that was simulated.
+        let mut chamber = Chamber::new(moves);
+        for _ in 0..cycle.equivalent_step(pieces) {
+            chamber.drop_next_piece();
+        }
+        frames.finish(|| chamber.board.show_tower());
+    }
+
+    cycle.extrapolate(pieces)
+}
+
+/// The number of rows at the top of the tower that the REPL shows.
+const REPL_ROWS: usize = 20;
+
+impl Simulation for Chamber<'_> {
+    /// Drop the next piece. The pieces never stop falling.
+    fn step(&mut self) -> bool {
+        self.drop_next_piece();
+        true
+    }
+
+    /// The whole tower, with the rows that the board has forgotten about, since nothing can
+    /// reach them any more, drawn as `~`, so that rows are numbered by their height in diffs.
+    fn render(&self) -> String {
+        let forgotten = format!("{}\n", "~".repeat(CHAMBER_WIDTH));
+        self.board.show_tower() + &forgotten.repeat(self.board.truncated_rows)
+    }
+
+    /// The top of the tower, with its height.
+    fn render_brief(&self) -> String {
+        let tower = self.board.show_tower();
+        let mut rows = tower.lines().take(REPL_ROWS).join("\n");
+        if self.board.height() > REPL_ROWS {
+            rows += "\n...";
+        }
+        format!(
+            "{rows}\npieces: {}, height: {}",
+            self.pieces,
+            self.board.height()
+        )
+    }
+}
+
+/// Commands for watching the tower grow, where each step drops a piece.
+pub fn repl(moves: &[Move]) -> Repl<'_, Timeline<Chamber<'_>>> {
+    timeline::repl(17, Chamber::new(moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse::Source;
+    use common::visualize::Frames;
+
+    use super::{parse_input, solve_part1, solve_part2, Chamber};
+
+    #[test]
+    fn rejects_an_empty_jet_pattern() {
+        let error = parse_input(Source::new("input.txt", "\n")).unwrap_err();
+        assert_eq!(error.expected, "a jet pattern");
+        assert!(error.to_string().contains("found end of input"), "{error}");
+    }
+
+    /// The tower after the first ten pieces, as drawn in the puzzle.
+    #[test]
+    fn shows_the_example_tower() {
+        let moves = parse_input(Source::new("example.txt", include_str!("../example.txt")));
+        let moves = moves.unwrap();
+        let mut chamber = Chamber::new(&moves);
+        for _ in 0..10 {
+            chamber.drop_next_piece();
+        }
+        insta::assert_snapshot!(chamber.board.show_tower());
+    }
+
+    /// The jets never push a piece against the right wall, so the last column stays empty
+    /// all the way down and the open space below the top never repeats.
+    #[test]
+    fn finds_a_cycle_above_a_column_that_never_fills() {
+        let moves = parse_input(Source::new("input.txt", "<<><<><<>><><<<<>>><\n")).unwrap();
+        let simulated = solve_part1(&moves, 30_000, &mut Frames::disabled());
+        assert_eq!(
+            solve_part2(&moves, 30_000, &mut Frames::disabled()),
+            simulated
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    io,
+};
+
+use common::image::{self, PngExport};
+use common::parse::{ParseError, Source};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 18)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<(i64, i64, i64)>, ParseError> {
+    source
+        .lines()
+        .map(|x| {
+            let coordinates: Vec<i64> = x
+                .split(',')
+                .map(|num| source.parse_at(num, "a coordinate"))
+                .collect::<Result<_, _>>()?;
+            coordinates
+                .into_iter()
+                .collect_tuple()
+                .ok_or_else(|| source.error_at(x, "three comma-separated coordinates"))
+        })
+        .collect()
+}
+
+#[aoc(day = 18, part = 1)]
+pub fn solve_part1(data: &[(i64, i64, i64)]) -> usize {
+    let cubes: BTreeSet<_> = data.iter().copied().collect();
+    let mut faces = data.len() * 6;
+
+    for cube in data {
+        for i in [-1, 1] {
+            let (x, y, z) = *cube;
+            if cubes.contains(&(x + i, y, z)) {
+                faces -= 1;
+            }
+            if cubes.contains(&(x, y + i, z)) {
+                faces -= 1;
+            }
+            if cubes.contains(&(x, y, z + i)) {
+                faces -= 1;
+            }
+        }
+    }
+
+    faces
+}
+
+pub type Point3D = (i64, i64, i64);
+
+fn calculate_bounding_box(cubes: &[Point3D]) -> (Point3D, Point3D) {
+    let minx = cubes.iter().copied().map(|(x, _, _)| x).min().unwrap();
+    let miny = cubes.iter().copied().map(|(_, y, _)| y).min().unwrap();
+    let minz = cubes.iter().copied().map(|(_, _, z)| z).min().unwrap();
+
+    let maxx = cubes.iter().copied().map(|(x, _, _)| x).max().unwrap();
+    let maxy = cubes.iter().copied().map(|(_, y, _)| y).max().unwrap();
+    let maxz = cubes.iter().copied().map(|(_, _, z)| z).max().unwrap();
+
+    ((minx, miny, minz), (maxx, maxy, maxz))
+}
+
+fn inside_bounding_box(bounding_box: (Point3D, Point3D), cube: Point3D) -> bool {
+    let (x, y, z) = cube;
+    let ((minx, miny, minz), (maxx, maxy, maxz)) = bounding_box;
+
+    x >= minx && x <= maxx && y >= miny && y <= maxy && z >= minz && z <= maxz
+}
+
+fn count_visible(
+    cubes: &BTreeSet<Point3D>,
+    bounding_box: (Point3D, Point3D),
+    visited: &mut BTreeSet<Point3D>,
+    current: Point3D,
+) -> usize {
+    if !visited.insert(current) {
+        // Already visited previously.
+        return 0;
+    }
+
+    let mut visible = 0;
+    for i in [-1, 1] {
+        let (x, y, z) = current;
+
+        for next_cube in [(x + i, y, z), (x, y + i, z), (x, y, z + i)] {
+            if inside_bounding_box(bounding_box, next_cube) {
+                if cubes.contains(&next_cube) {
+                    visible += 1;
+                } else {
+                    visible += count_visible(cubes, bounding_box, visited, next_cube);
+                }
+            }
+        }
+    }
+
+    visible
+}
+
+#[aoc(day = 18, part = 2)]
+pub fn solve_part2(data: &[Point3D]) -> usize {
+    let bounding_box = calculate_bounding_box(data);
+    let ((minx, miny, minz), (maxx, maxy, maxz)) = bounding_box;
+
+    let cubes: BTreeSet<_> = data.iter().copied().collect();
+    let mut visible = 0;
+    let mut visited: BTreeSet<_> = Default::default();
+
+    for y in miny..=maxy {
+        for z in minz..=maxz {
+            visible += count_visible(&cubes, bounding_box, &mut visited, (minx - 1, y, z));
+            visible += count_visible(&cubes, bounding_box, &mut visited, (maxx + 1, y, z));
+        }
+    }
+    for x in minx..=maxx {
+        for y in miny..=maxy {
+            visible += count_visible(&cubes, bounding_box, &mut visited, (x, y, minz - 1));
+            visible += count_visible(&cubes, bounding_box, &mut visited, (x, y, maxz + 1));
+        }
+    }
+    for x in minx..=maxx {
+        for z in minz..=maxz {
+            visible += count_visible(&cubes, bounding_box, &mut visited, (x, miny - 1, z));
+            visible += count_visible(&cubes, bounding_box, &mut visited, (x, maxy + 1, z));
+        }
+    }
+
+    visible
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Lava,
+    Air,
+    Pocket,
+}
+
+/// Draw the droplet's cross sections, with the lava in orange, the air around it in dark
+/// blue, and the pockets of air trapped inside it in light blue.
+pub fn draw_cross_sections(data: &[Point3D], png: &PngExport) -> io::Result<()> {
+    png.write(&cross_sections(data), |cell| match cell {
+        Some(Cell::Lava) => [230, 90, 30],
+        Some(Cell::Air) => [40, 40, 70],
+        Some(Cell::Pocket) => [80, 200, 230],
+        None => image::BACKGROUND,
+    })
+}
+
+/// Slices of the droplet at each `z` coordinate, laid out left to right and top to bottom,
+/// showing the lava, the air around it, and the air pockets trapped inside it.
+pub fn cross_sections(data: &[Point3D]) -> Vec<Vec<Option<Cell>>> {
+    let cubes: BTreeSet<_> = data.iter().copied().collect();
+    let ((minx, miny, minz), (maxx, maxy, maxz)) = calculate_bounding_box(data);
+    let bounding_box = (
+        (minx - 1, miny - 1, minz - 1),
+        (maxx + 1, maxy + 1, maxz + 1),
+    );
+
+    // Flood fill the air from outside the droplet. Any other air is trapped inside it.
+    let mut outside: BTreeSet<_> = [bounding_box.0].into_iter().collect();
+    let mut queue = VecDeque::from([bounding_box.0]);
+    while let Some((x, y, z)) = queue.pop_front() {
+        for i in [-1, 1] {
+            for next in [(x + i, y, z), (x, y + i, z), (x, y, z + i)] {
+                if inside_bounding_box(bounding_box, next)
+                    && !cubes.contains(&next)
+                    && outside.insert(next)
+                {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    let slice_height = (maxx - minx + 1) as usize;
+    let slice_width = (maxy - miny + 1) as usize;
+    let slice_count = (maxz - minz + 1) as usize;
+    let columns = (slice_count as f64).sqrt().ceil() as usize;
+    let rows = slice_count.div_ceil(columns);
+
+    // Leave a gap of one cell between the slices.
+    let mut result = vec![vec![None; columns * (slice_width + 1)]; rows * (slice_height + 1)];
+    for (idx, z) in (minz..=maxz).enumerate() {
+        let (top, left) = (
+            idx / columns * (slice_height + 1),
+            idx % columns * (slice_width + 1),
+        );
+        for (i, x) in (minx..=maxx).enumerate() {
+            for (j, y) in (miny..=maxy).enumerate() {
+                let cell = if cubes.contains(&(x, y, z)) {
+                    Cell::Lava
+                } else if outside.contains(&(x, y, z)) {
+                    Cell::Air
+                } else {
+                    Cell::Pocket
+                };
+                result[top + i][left + j] = Some(cell);
+            }
+        }
+    }
+    result
+}
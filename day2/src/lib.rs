@@ -0,0 +1,156 @@
+use std::io::BufRead;
+
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    fn value(&self) -> u64 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+
+    // +1 = self win, -1 = self loss, 0 = tie
+    fn outcome(&self, other: Shape) -> i64 {
+        if self == &other {
+            return 0;
+        }
+
+        match self {
+            Shape::Rock => {
+                if other == Shape::Paper {
+                    -1
+                } else {
+                    1
+                }
+            }
+            Shape::Paper => {
+                if other == Shape::Scissors {
+                    -1
+                } else {
+                    1
+                }
+            }
+            Shape::Scissors => {
+                if other == Shape::Rock {
+                    -1
+                } else {
+                    1
+                }
+            }
+        }
+    }
+}
+
+fn score_round(opponent_play: Shape, my_play: Shape) -> u64 {
+    let play_value = my_play.value();
+
+    let outcome_value = match my_play.outcome(opponent_play) {
+        -1 => 0,
+        0 => 3,
+        1 => 6,
+        _ => unreachable!(),
+    };
+
+    play_value + outcome_value
+}
+
+fn suggest_play(opponent_play: Shape, outcome: i64) -> Shape {
+    for shape in [Shape::Rock, Shape::Paper, Shape::Scissors] {
+        if shape.outcome(opponent_play) == outcome {
+            return shape;
+        }
+    }
+    unreachable!()
+}
+
+fn parse_round<'a>(source: Source<'a>, line: &'a str) -> Result<(&'a str, &'a str), ParseError> {
+    let (other, me) = source.split_once(line.trim(), " ")?;
+    Ok((
+        source.one_of(other, &["A", "B", "C"])?,
+        source.one_of(me, &["X", "Y", "Z"])?,
+    ))
+}
+
+#[aoc_parser(day = 2)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<(&str, &str)>, ParseError> {
+    source.lines().map(|x| parse_round(source, x)).collect()
+}
+
+fn score_part1((other, me): (&str, &str)) -> u64 {
+    let opponent_play = match other {
+        "A" => Shape::Rock,
+        "B" => Shape::Paper,
+        "C" => Shape::Scissors,
+        _ => unreachable!(),
+    };
+    let my_play = match me {
+        "X" => Shape::Rock,
+        "Y" => Shape::Paper,
+        "Z" => Shape::Scissors,
+        _ => unreachable!(),
+    };
+
+    score_round(opponent_play, my_play)
+}
+
+fn score_part2((other, me): (&str, &str)) -> u64 {
+    let opponent_play = match other {
+        "A" => Shape::Rock,
+        "B" => Shape::Paper,
+        "C" => Shape::Scissors,
+        _ => unreachable!(),
+    };
+    let outcome = match me {
+        "X" => -1,
+        "Y" => 0,
+        "Z" => 1,
+        _ => unreachable!(),
+    };
+    let my_play = suggest_play(opponent_play, outcome);
+
+    score_round(opponent_play, my_play)
+}
+
+#[aoc(day = 2, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &[(&str, &str)]) -> u64 {
+    data.iter().copied().map(score_part1).sum()
+}
+
+#[aoc(day = 2, part = 2, stream = stream_part2)]
+pub fn solve_part2(data: &[(&str, &str)]) -> u64 {
+    data.iter().copied().map(score_part2).sum()
+}
+
+/// Total the scores of the rounds as they are read, without keeping the strategy guide.
+fn stream_total<R: BufRead>(
+    lines: &mut LineReader<R>,
+    score: impl Fn((&str, &str)) -> u64,
+) -> Result<u64, StreamError> {
+    let mut total = 0;
+    lines.try_for_each(|line| {
+        total += score(parse_round(line, line.text())?);
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<u64, StreamError> {
+    stream_total(lines, score_part1)
+}
+
+pub fn stream_part2<R: BufRead>(lines: &mut LineReader<R>) -> Result<u64, StreamError> {
+    stream_total(lines, score_part2)
+}
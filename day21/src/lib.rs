@@ -0,0 +1,423 @@
+use std::{collections::BTreeMap, fmt};
+
+use common::num::{ArithmeticError, Int};
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation<'a> {
+    Literal(i64),
+    Add(&'a str, &'a str),
+    Sub(&'a str, &'a str),
+    Mul(&'a str, &'a str),
+    Div(&'a str, &'a str),
+}
+
+impl<'a> Operation<'a> {
+    pub fn parse(source: Source<'a>, rest: &'a str) -> Result<Self, ParseError> {
+        let operation = if let Ok(num) = rest.parse() {
+            Operation::Literal(num)
+        } else if let Some((left, right)) = rest.split_once(" + ") {
+            Operation::Add(left, right)
+        } else if let Some((left, right)) = rest.split_once(" - ") {
+            Operation::Sub(left, right)
+        } else if let Some((left, right)) = rest.split_once(" * ") {
+            Operation::Mul(left, right)
+        } else if let Some((left, right)) = rest.split_once(" / ") {
+            Operation::Div(left, right)
+        } else {
+            return Err(source.error_at(rest, "a number or a binary operation"));
+        };
+        Ok(operation)
+    }
+
+    /// Apply a binary operation to the values of its operands.
+    pub fn apply<N: Int>(&self, left: &N, right: &N) -> Result<N, ArithmeticError> {
+        match self {
+            Operation::Literal(_) => unreachable!("{self:?}"),
+            Operation::Add(_, _) => left.checked_add(right),
+            Operation::Sub(_, _) => left.checked_sub(right),
+            Operation::Mul(_, _) => left.checked_mul(right),
+            Operation::Div(_, _) => left.checked_div(right),
+        }
+    }
+
+    pub fn operands(&self) -> Option<(&'a str, &'a str)> {
+        match *self {
+            Operation::Literal(_) => None,
+            Operation::Add(l, r)
+            | Operation::Sub(l, r)
+            | Operation::Mul(l, r)
+            | Operation::Div(l, r) => Some((l, r)),
+        }
+    }
+}
+
+impl fmt::Display for Operation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Literal(num) => write!(f, "{num}"),
+            Operation::Add(l, r) => write!(f, "{l} + {r}"),
+            Operation::Sub(l, r) => write!(f, "{l} - {r}"),
+            Operation::Mul(l, r) => write!(f, "{l} * {r}"),
+            Operation::Div(l, r) => write!(f, "{l} / {r}"),
+        }
+    }
+}
+
+#[aoc_parser(day = 21)]
+pub fn parse_input(source: Source<'_>) -> Result<BTreeMap<&str, Operation<'_>>, ParseError> {
+    let operations: BTreeMap<&str, Operation<'_>> = source
+        .lines()
+        .map(|x| {
+            let (name, rest) = source.split_once(x, ": ")?;
+            Ok((name, Operation::parse(source, rest)?))
+        })
+        .collect::<Result<_, _>>()?;
+
+    for (left, right) in operations.values().filter_map(Operation::operands) {
+        for operand in [left, right] {
+            if !operations.contains_key(operand) {
+                return Err(source.error_at(operand, "the name of a monkey"));
+            }
+        }
+    }
+    for name in ["root", "humn"] {
+        if !operations.contains_key(name) {
+            let end = &source.text()[source.text().len()..];
+            return Err(source.error_at(end, format!("a monkey named `{name}`")));
+        }
+    }
+
+    Ok(operations)
+}
+
+fn evaluate<'a, N: Int>(
+    name: &'a str,
+    values: &mut BTreeMap<&'a str, N>,
+    operations: &BTreeMap<&'a str, Operation<'a>>,
+) -> Result<N, ArithmeticError> {
+    if let Some(num) = values.get(name) {
+        return Ok(num.clone());
+    }
+
+    let value = match operations[&name] {
+        Operation::Literal(l) => N::from_i64(l),
+        op => {
+            let (l, r) = op.operands().unwrap();
+            let left = evaluate(l, values, operations)?;
+            let right = evaluate(r, values, operations)?;
+            op.apply(&left, &right)?
+        }
+    };
+    values.insert(name, value.clone());
+
+    Ok(value)
+}
+
+#[aoc(day = 21, part = 1)]
+pub fn solve_part1<N: Int>(
+    operations: &BTreeMap<&str, Operation<'_>>,
+) -> Result<N, ArithmeticError> {
+    let mut values: BTreeMap<&str, N> = operations
+        .iter()
+        .filter_map(|(k, v)| {
+            if let Operation::Literal(num) = v {
+                Some((*k, N::from_i64(*num)))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    evaluate("root", &mut values, operations)
+}
+
+fn evaluate_except_special<'a, N: Int>(
+    name: &'a str,
+    values: &mut BTreeMap<&'a str, N>,
+    operations: &BTreeMap<&'a str, Operation<'a>>,
+) -> Result<Option<N>, ArithmeticError> {
+    if let Some(num) = values.get(name) {
+        return Ok(Some(num.clone()));
+    }
+
+    let value = match operations[&name] {
+        Operation::Literal(l) => N::from_i64(l),
+        op => {
+            let (l, r) = op.operands().unwrap();
+            let Some(left) = evaluate_except_special(l, values, operations)? else {
+                return Ok(None);
+            };
+            let Some(right) = evaluate_except_special(r, values, operations)? else {
+                return Ok(None);
+            };
+            op.apply(&left, &right)?
+        }
+    };
+
+    // Return late so that we evaluate as many expressions as possible.
+    if name == "humn" || name == "root" {
+        return Ok(None);
+    }
+
+    values.insert(name, value.clone());
+
+    Ok(Some(value))
+}
+
+/// Why an operand couldn't be worked out from the result of its operation.
+#[derive(Debug)]
+enum BackwardError {
+    /// No integer operand gives the expected result.
+    Inexact,
+    Arithmetic(ArithmeticError),
+}
+
+impl From<ArithmeticError> for BackwardError {
+    fn from(error: ArithmeticError) -> Self {
+        BackwardError::Arithmetic(error)
+    }
+}
+
+/// Pass on an arithmetic error from working backwards, but give up on an inexact result,
+/// which the puzzle promises can't happen.
+fn expect_exact(result: Result<(), BackwardError>, message: &str) -> Result<(), ArithmeticError> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(BackwardError::Arithmetic(e)) => Err(e),
+        Err(BackwardError::Inexact) => panic!("{message}"),
+    }
+}
+
+fn evaluate_backward<'a, N: Int>(
+    name: &'a str,
+    expected_result: N,
+    values: &mut BTreeMap<&'a str, N>,
+    operations: &BTreeMap<&'a str, Operation<'a>>,
+) -> Result<(), BackwardError> {
+    match operations[&name] {
+        Operation::Literal(num) => {
+            if name != "humn" {
+                assert_eq!(expected_result, N::from_i64(num));
+                Ok(())
+            } else {
+                Ok(())
+            }
+        }
+        op => {
+            let (l, r) = op.operands().unwrap();
+            let maybe_left = values.get(l).cloned();
+            let maybe_right = values.get(r).cloned();
+            match (maybe_left, maybe_right) {
+                (Some(num), None) => {
+                    let other = match op {
+                        Operation::Literal(_) => unreachable!(),
+                        Operation::Add(_, _) => expected_result.checked_sub(&num)?,
+                        Operation::Sub(_, _) => num.checked_sub(&expected_result)?,
+                        Operation::Mul(_, _) => {
+                            if num.is_zero() {
+                                return Err(BackwardError::Inexact);
+                            } else {
+                                let value = expected_result.checked_div(&num)?;
+                                if value.checked_mul(&num)? != expected_result {
+                                    return Err(BackwardError::Inexact);
+                                }
+                                value
+                            }
+                        }
+                        Operation::Div(_, _) => {
+                            if expected_result.is_zero() {
+                                return Err(BackwardError::Inexact);
+                            } else {
+                                num.checked_div(&expected_result)?
+                            }
+                        }
+                    };
+                    values.insert(r, other.clone());
+                    let result = evaluate_backward(r, other, values, operations);
+                    if result.is_err() {
+                        values.remove(r);
+                    }
+                    result
+                }
+                (None, Some(num)) => {
+                    let other = match op {
+                        Operation::Literal(_) => unreachable!(),
+                        Operation::Add(_, _) => expected_result.checked_sub(&num)?,
+                        Operation::Sub(_, _) => expected_result.checked_add(&num)?,
+                        Operation::Mul(_, _) => {
+                            if expected_result.is_zero() {
+                                return Err(BackwardError::Inexact);
+                            } else {
+                                let value = expected_result.checked_div(&num)?;
+                                if num.checked_mul(&value)? != expected_result {
+                                    return Err(BackwardError::Inexact);
+                                }
+                                value
+                            }
+                        }
+                        Operation::Div(_, _) => expected_result.checked_mul(&num)?,
+                    };
+                    values.insert(l, other.clone());
+                    let result = evaluate_backward(l, other, values, operations);
+                    if result.is_err() {
+                        values.remove(l);
+                    }
+                    result
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+#[aoc(day = 21, part = 2)]
+pub fn solve_part2<N: Int>(
+    operations: &BTreeMap<&str, Operation<'_>>,
+) -> Result<N, ArithmeticError> {
+    let mut values: BTreeMap<&str, N> = operations
+        .iter()
+        .filter_map(|(k, v)| {
+            if let Operation::Literal(num) = v {
+                (*k != "root" && *k != "humn").then(|| (*k, N::from_i64(*num)))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let (left, right) = match operations["root"] {
+        Operation::Literal(_) => unreachable!(),
+        Operation::Add(left, right)
+        | Operation::Sub(left, right)
+        | Operation::Mul(left, right)
+        | Operation::Div(left, right) => (left, right),
+    };
+
+    let left_result = evaluate_except_special(left, &mut values, operations)?;
+    let right_result = evaluate_except_special(right, &mut values, operations)?;
+
+    match (left_result, right_result) {
+        (Some(left), None) => {
+            values.insert(right, left.clone());
+            expect_exact(
+                evaluate_backward(right, left, &mut values, operations),
+                "right failed",
+            )?;
+        }
+        (None, Some(right)) => {
+            values.insert(left, right.clone());
+            expect_exact(
+                evaluate_backward(left, right, &mut values, operations),
+                "left failed",
+            )?;
+        }
+        (left_result, right_result) => unreachable!("{left_result:?} {right_result:?}"),
+    }
+
+    while !values.contains_key("humn") {
+        for name in operations.keys().copied() {
+            if name != "root" && name != "humn" {
+                evaluate_except_special(name, &mut values, operations)?;
+            }
+        }
+
+        for name in operations.keys().copied() {
+            let Some(op_value) = values.get(name).cloned() else {
+                continue;
+            };
+            expect_exact(
+                evaluate_backward(name, op_value, &mut values, operations),
+                "backward computation failed",
+            )?;
+        }
+    }
+
+    Ok(values["humn"].clone())
+}
+
+fn find_monkey<'a>(
+    operations: &BTreeMap<&'a str, Operation<'a>>,
+    args: &[&str],
+) -> Result<&'a str, String> {
+    let name: String = repl::arg(args, 0, "the name of a monkey")?;
+    match operations.get_key_value(name.as_str()) {
+        Some((name, _)) => Ok(*name),
+        None => Err(format!("there's no monkey named `{name}`")),
+    }
+}
+
+/// Commands for exploring the monkeys' jobs, remembering the numbers they've yelled.
+pub fn repl<'a>(
+    operations: &'a BTreeMap<&'a str, Operation<'a>>,
+) -> Repl<'a, BTreeMap<&'a str, i64>> {
+    Repl::new(21, BTreeMap::<&str, i64>::new())
+        .command("job", "<monkey>", "show what the monkey does", |_, args| {
+            let name = find_monkey(operations, args)?;
+            Ok(format!("{name}: {}", operations[name]))
+        })
+        .command(
+            "eval",
+            "<monkey>",
+            "work out the number the monkey yells",
+            |values, args| {
+                let name = find_monkey(operations, args)?;
+                let value = evaluate(name, values, operations).map_err(|e| e.to_string())?;
+                Ok(value.to_string())
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use common::num::{ArithmeticError, BigInt, Int};
+    use common::parse::Source;
+
+    use super::{parse_input, solve_part1, solve_part2};
+
+    /// Monkeys whose numbers only fit in 128 bits: `root` compares 5000000000 squared with
+    /// 5000000000 times what the human yells.
+    const LARGE: &str = "\
+root: aaaa + bbbb
+aaaa: humn * cccc
+bbbb: cccc * dddd
+cccc: 5000000000
+dddd: 5000000000
+humn: 5
+";
+
+    #[test]
+    fn example() {
+        let operations =
+            parse_input(Source::new("example.txt", include_str!("../example.txt"))).unwrap();
+        assert_eq!(solve_part1::<i64>(&operations), Ok(152));
+        assert_eq!(solve_part2::<i64>(&operations), Ok(301));
+    }
+
+    #[test]
+    fn overflowing_i64_is_an_error_and_wider_types_succeed() {
+        let operations = parse_input(Source::new("large.txt", LARGE)).unwrap();
+
+        let error = solve_part1::<i64>(&operations).unwrap_err();
+        assert_eq!(
+            error,
+            ArithmeticError::Overflow {
+                expression: "5000000000 * 5000000000".to_string(),
+                int_type: "i64",
+            }
+        );
+        assert!(solve_part2::<i64>(&operations).is_err());
+
+        assert_eq!(
+            solve_part1::<i128>(&operations),
+            Ok(25_000_000_025_000_000_000)
+        );
+        assert_eq!(solve_part2::<i128>(&operations), Ok(5_000_000_000));
+        let humn = solve_part2::<BigInt>(&operations).unwrap();
+        assert_eq!(humn.to_i64(), Some(5_000_000_000));
+    }
+}
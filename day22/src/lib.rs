@@ -0,0 +1,22 @@
+use common::parse::{ParseError, Source};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 22)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<u64>, ParseError> {
+    source
+        .lines()
+        .map(|x| source.parse_at(x, "a number"))
+        .collect()
+}
+
+#[aoc(day = 22, part = 1)]
+pub fn solve_part1(_data: &[u64]) -> usize {
+    todo!()
+}
+
+#[aoc(day = 22, part = 2)]
+pub fn solve_part2(_data: &[u64]) -> usize {
+    todo!()
+}
@@ -0,0 +1,538 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::rc::Rc;
+
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::timeline::{self, Simulation, Timeline};
+use common::visualize::Frames;
+use common::{aoc, aoc_parser};
+use geometry::{Direction, Vector2D};
+#[allow(unused_imports)]
+use itertools::Itertools;
+use tracing::debug;
+
+pub mod geometry;
+
+#[aoc_parser(day = 24)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<&str>, ParseError> {
+    let data = source.lines().map(|x| x.trim()).collect_vec();
+    let width = data[0].len();
+    let last_row = data.len() - 1;
+
+    for (i, row) in data.iter().enumerate() {
+        let valid_tiles: &[char] = if i == 0 || i == last_row {
+            &['#', '.']
+        } else {
+            &['#', '.', '>', '<', '^', 'v']
+        };
+        if let Some((j, c)) = row.char_indices().find(|(_, c)| !valid_tiles.contains(c)) {
+            let tile = &row[j..j + c.len_utf8()];
+            return Err(source.error_at(tile, "a wall, an open tile or a blizzard"));
+        }
+        if row.len() != width {
+            let position = &row[width.min(row.len())..];
+            return Err(source.error_at(position, format!("a row {width} tiles wide")));
+        }
+        if (i == 0 || i == last_row) && row.matches('.').count() != 1 {
+            return Err(source.error_at(row, "a wall with exactly one opening"));
+        }
+    }
+
+    Ok(data)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blizzard {
+    initial_position: Vector2D,
+    direction: Direction,
+    cycle_length: usize,
+}
+
+impl Blizzard {
+    fn location_at_time_step(&self, time_step: usize) -> Vector2D {
+        match self.direction {
+            Direction::Up | Direction::Down => {
+                let delta = match self.direction {
+                    Direction::Up => -1,
+                    Direction::Down => 1,
+                    _ => unreachable!(),
+                };
+                let dx = ((self.cycle_length.checked_add_signed(delta).unwrap()) * time_step)
+                    % self.cycle_length;
+                let base_x = (self.initial_position.x - 1) as usize;
+
+                let final_x = (base_x + dx) % self.cycle_length + 1;
+                Vector2D::new(final_x as i64, self.initial_position.y)
+            }
+            Direction::Left | Direction::Right => {
+                let delta = match self.direction {
+                    Direction::Left => -1,
+                    Direction::Right => 1,
+                    _ => unreachable!(),
+                };
+                let dy = ((self.cycle_length.checked_add_signed(delta).unwrap()) * time_step)
+                    % self.cycle_length;
+                let base_y = (self.initial_position.y - 1) as usize;
+
+                let final_y = (base_y + dy) % self.cycle_length + 1;
+                Vector2D::new(self.initial_position.x, final_y as i64)
+            }
+        }
+    }
+}
+
+pub struct BlizzardMap {
+    start: Vector2D,
+    end: Vector2D,
+    blizzards_by_row: BTreeMap<i64, Vec<Blizzard>>,
+    blizzards_by_col: BTreeMap<i64, Vec<Blizzard>>,
+    wall_x: i64,
+    wall_y: i64,
+}
+
+impl BlizzardMap {
+    fn new(data: &[&str]) -> Self {
+        let wall_x = (data.len() - 1) as i64;
+        let wall_y = (data[0].len() - 1) as i64;
+
+        let start = data[0]
+            .chars()
+            .enumerate()
+            .filter_map(|(i, tile)| match tile {
+                '.' => Some(Vector2D::new(0, i as i64)),
+                '#' => None,
+                _ => unreachable!(),
+            })
+            .at_most_one()
+            .expect("no more than one element")
+            .expect("exactly one element");
+        let end = data
+            .last()
+            .unwrap()
+            .chars()
+            .enumerate()
+            .filter_map(|(i, tile)| match tile {
+                '.' => Some(Vector2D::new(wall_x, i as i64)),
+                '#' => None,
+                _ => unreachable!(),
+            })
+            .at_most_one()
+            .expect("no more than one element")
+            .expect("exactly one element");
+
+        let mut blizzards_by_row: BTreeMap<_, Vec<_>> = Default::default();
+        let mut blizzards_by_col: BTreeMap<_, Vec<_>> = Default::default();
+
+        for (i, row) in data.iter().enumerate() {
+            for (j, tile) in row.chars().enumerate() {
+                let position = Vector2D::new(i as i64, j as i64);
+                let horizontal_cycle_length = data[0].len() - 2;
+                let vertical_cycle_length = data.len() - 2;
+                let maybe_blizzard = match tile {
+                    '.' | '#' => None,
+                    '>' => Some(Blizzard {
+                        initial_position: position,
+                        direction: Direction::Right,
+                        cycle_length: horizontal_cycle_length,
+                    }),
+                    '<' => Some(Blizzard {
+                        initial_position: position,
+                        direction: Direction::Left,
+                        cycle_length: horizontal_cycle_length,
+                    }),
+                    '^' => Some(Blizzard {
+                        initial_position: position,
+                        direction: Direction::Up,
+                        cycle_length: vertical_cycle_length,
+                    }),
+                    'v' => Some(Blizzard {
+                        initial_position: position,
+                        direction: Direction::Down,
+                        cycle_length: vertical_cycle_length,
+                    }),
+                    _ => unreachable!("{i} {j} {tile}"),
+                };
+
+                if let Some(blizzard) = maybe_blizzard {
+                    match blizzard.direction {
+                        Direction::Up | Direction::Down => {
+                            blizzards_by_col.entry(j as i64).or_default().push(blizzard);
+                        }
+                        Direction::Left | Direction::Right => {
+                            blizzards_by_row.entry(i as i64).or_default().push(blizzard);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            start,
+            end,
+            blizzards_by_row,
+            blizzards_by_col,
+            wall_x,
+            wall_y,
+        }
+    }
+
+    fn tile_available_at_time_step(&self, tile: Vector2D, time_step: usize) -> bool {
+        if tile.x < 0 || tile.y < 0 || tile.x > self.wall_x || tile.y > self.wall_y {
+            // Spilled off the map, perhaps through the start or end tiles.
+            return false;
+        }
+
+        if tile.y == 0 || tile.y == self.wall_y {
+            // Hit the side walls.
+            return false;
+        }
+
+        if tile.x == 0 {
+            // Either hit a wall, or came back to the start.
+            return tile == self.start;
+        }
+
+        if tile.x == self.wall_x {
+            // Either hit a wall, or reached the end.
+            return tile == self.end;
+        }
+
+        let mut blizzards_to_check = self
+            .blizzards_by_row
+            .get(&tile.x)
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .chain(
+                self.blizzards_by_col
+                    .get(&tile.y)
+                    .map(|x| x.as_slice())
+                    .unwrap_or_default(),
+            );
+        !blizzards_to_check.any(|blizzard| blizzard.location_at_time_step(time_step) == tile)
+    }
+
+    /// Draw the valley at a time step like the puzzle does, with the places the expedition
+    /// could be marked with `E`.
+    fn render(&self, time_step: usize, expedition: &BTreeSet<Vector2D>) -> String {
+        let mut blizzards: BTreeMap<Vector2D, Vec<Direction>> = Default::default();
+        for blizzard in self
+            .blizzards_by_row
+            .values()
+            .chain(self.blizzards_by_col.values())
+            .flatten()
+        {
+            blizzards
+                .entry(blizzard.location_at_time_step(time_step))
+                .or_default()
+                .push(blizzard.direction);
+        }
+
+        let mut result = String::new();
+        for x in 0..=self.wall_x {
+            for y in 0..=self.wall_y {
+                let tile = Vector2D::new(x, y);
+                let is_wall = x == 0 || y == 0 || x == self.wall_x || y == self.wall_y;
+                result.push(if expedition.contains(&tile) {
+                    'E'
+                } else if tile == self.start || tile == self.end {
+                    '.'
+                } else if is_wall {
+                    '#'
+                } else {
+                    match blizzards.get(&tile).map(|b| b.as_slice()) {
+                        None => '.',
+                        Some([Direction::Up]) => '^',
+                        Some([Direction::Down]) => 'v',
+                        Some([Direction::Left]) => '<',
+                        Some([Direction::Right]) => '>',
+                        Some(overlapping) => {
+                            char::from_digit(overlapping.len() as u32, 10).unwrap_or('*')
+                        }
+                    }
+                });
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+#[aoc(day = 24, part = 1)]
+pub fn solve_part1(data: &[&str], frames: &mut Frames) -> usize {
+    let blizzard_map = BlizzardMap::new(data);
+
+    let start = (0, blizzard_map.start);
+    let mut visited: BTreeSet<(usize, Vector2D)> = [start].into_iter().collect();
+    let mut queue: VecDeque<_> = [start].into_iter().collect();
+
+    let moves = [
+        Vector2D::new(0, 0), // stall and don't move for a turn
+        Direction::Up.unit_vector(),
+        Direction::Down.unit_vector(),
+        Direction::Left.unit_vector(),
+        Direction::Right.unit_vector(),
+    ];
+
+    let mut solution = None;
+
+    let mut explored_time = None;
+    'outer: while let Some((time, position)) = queue.pop_front() {
+        let next_time = time + 1;
+        if explored_time != Some(time) {
+            // The queue holds every other place the expedition could be at this time.
+            explored_time = Some(time);
+            debug!(time, frontier = queue.len() + 1, "exploring time step");
+            frames.push(|| {
+                let expedition = queue.iter().map(|(_, p)| *p).chain([position]).collect();
+                blizzard_map.render(time, &expedition)
+            });
+        }
+
+        for next_move in moves.iter().copied() {
+            let next_position = position + next_move;
+            let next_key = (next_time, next_position);
+            if visited.contains(&next_key) {
+                continue;
+            }
+
+            if blizzard_map.tile_available_at_time_step(next_position, next_time) {
+                visited.insert(next_key);
+
+                if next_position == blizzard_map.end {
+                    solution = Some(next_key);
+                    break 'outer;
+                } else {
+                    queue.push_back(next_key);
+                }
+            }
+        }
+    }
+
+    let (time, position) = solution.expect("no solution found");
+    frames.finish(|| blizzard_map.render(time, &[position].into_iter().collect()));
+    time
+}
+
+#[aoc(day = 24, part = 2)]
+pub fn solve_part2(data: &[&str], frames: &mut Frames) -> usize {
+    let blizzard_map = BlizzardMap::new(data);
+
+    let start = (0, 0, blizzard_map.start);
+    let mut visited: BTreeSet<(usize, usize, Vector2D)> = [start].into_iter().collect();
+    let mut queue: VecDeque<_> = [start].into_iter().collect();
+
+    let moves = [
+        Vector2D::new(0, 0), // stall and don't move for a turn
+        Direction::Up.unit_vector(),
+        Direction::Down.unit_vector(),
+        Direction::Left.unit_vector(),
+        Direction::Right.unit_vector(),
+    ];
+
+    let mut solution = None;
+
+    let mut furthest_leg = 0;
+    let mut explored_time = None;
+    'outer: while let Some((leg, time, position)) = queue.pop_front() {
+        let next_time = time + 1;
+        if explored_time != Some(time) {
+            // The queue holds every other place the expedition could be at this time.
+            explored_time = Some(time);
+            frames.push(|| {
+                let expedition = queue.iter().map(|(_, _, p)| *p).chain([position]).collect();
+                blizzard_map.render(time, &expedition)
+            });
+        }
+
+        for next_move in moves.iter().copied() {
+            let next_position = position + next_move;
+
+            let next_leg = match leg {
+                0 => {
+                    if next_position == blizzard_map.end {
+                        leg + 1
+                    } else {
+                        leg
+                    }
+                }
+                1 => {
+                    if next_position == blizzard_map.start {
+                        leg + 1
+                    } else {
+                        leg
+                    }
+                }
+                2 => {
+                    if next_position == blizzard_map.end {
+                        leg + 1
+                    } else {
+                        leg
+                    }
+                }
+                _ => unreachable!("{leg}"),
+            };
+
+            let next_key = (next_leg, next_time, next_position);
+            if visited.contains(&next_key) {
+                continue;
+            }
+            if next_leg > furthest_leg {
+                furthest_leg = next_leg;
+                debug!(leg, time = next_time, "finished leg");
+            }
+
+            if blizzard_map.tile_available_at_time_step(next_position, next_time) {
+                visited.insert(next_key);
+
+                if next_leg == 3 && next_position == blizzard_map.end {
+                    solution = Some(next_key);
+                    break 'outer;
+                } else {
+                    queue.push_back(next_key);
+                }
+            }
+        }
+    }
+
+    let (_, time, position) = solution.expect("no solution found");
+    frames.finish(|| blizzard_map.render(time, &[position].into_iter().collect()));
+    time
+}
+
+/// Every place the expedition could be, a minute at a time, as it crosses the valley `trips`
+/// times: once for part 1, and there, back for the snacks and there again for part 2. As soon
+/// as it can reach the end of a trip, it's there and nowhere else.
+#[derive(Clone)]
+pub struct Expedition {
+    blizzard_map: Rc<BlizzardMap>,
+    trips: usize,
+    finished_trips: usize,
+    time: usize,
+    positions: BTreeSet<Vector2D>,
+}
+
+impl Expedition {
+    fn new(blizzard_map: Rc<BlizzardMap>, trips: usize) -> Self {
+        Self {
+            positions: [blizzard_map.start].into_iter().collect(),
+            blizzard_map,
+            trips,
+            finished_trips: 0,
+            time: 0,
+        }
+    }
+}
+
+impl Simulation for Expedition {
+    /// Let a minute pass, which finishes the simulation once the last trip is done.
+    fn step(&mut self) -> bool {
+        if self.finished_trips == self.trips {
+            return false;
+        }
+        let moves = [
+            Vector2D::new(0, 0), // stall and don't move for a turn
+            Direction::Up.unit_vector(),
+            Direction::Down.unit_vector(),
+            Direction::Left.unit_vector(),
+            Direction::Right.unit_vector(),
+        ];
+        self.time += 1;
+        self.positions = self
+            .positions
+            .iter()
+            .flat_map(|position| moves.iter().map(move |next_move| *position + *next_move))
+            .filter(|next| {
+                self.blizzard_map
+                    .tile_available_at_time_step(*next, self.time)
+            })
+            .collect();
+
+        let goal = if self.finished_trips.is_multiple_of(2) {
+            self.blizzard_map.end
+        } else {
+            self.blizzard_map.start
+        };
+        if self.positions.contains(&goal) {
+            self.finished_trips += 1;
+            debug!(
+                trip = self.finished_trips,
+                time = self.time,
+                "finished trip"
+            );
+            self.positions = [goal].into_iter().collect();
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        self.blizzard_map.render(self.time, &self.positions)
+    }
+}
+
+/// Commands for watching the expedition a minute at a time, crossing the valley once until
+/// told otherwise.
+pub fn repl(data: &[&str]) -> Repl<'static, Timeline<Expedition>> {
+    let blizzard_map = Rc::new(BlizzardMap::new(data));
+    timeline::repl(24, Expedition::new(blizzard_map.clone(), 1)).command(
+        "trips",
+        "<1|3>",
+        "start again, crossing the valley that many times",
+        move |timeline, args| {
+            let trips = repl::arg(args, 0, "a number of trips")?;
+            if trips == 0 {
+                return Err("expected at least one trip".to_string());
+            }
+            *timeline = Timeline::new(Expedition::new(blizzard_map.clone(), trips));
+            match trips {
+                1 => Ok("started again with 1 trip".to_string()),
+                _ => Ok(format!("started again with {trips} trips")),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse::Source;
+    use common::visualize::Frames;
+
+    use super::{parse_input, repl, solve_part1};
+
+    /// The valley when the expedition reaches the exit.
+    #[test]
+    fn renders_the_example_valley() {
+        let data = parse_input(Source::new("example.txt", include_str!("../example.txt")));
+        let mut frames = Frames::final_only();
+        solve_part1(&data.unwrap(), &mut frames);
+        insta::assert_snapshot!(frames.frames().last().unwrap());
+    }
+
+    #[test]
+    fn rejects_a_non_ascii_tile() {
+        let error = parse_input(Source::new("input.txt", "#.#\n#é#\n#.#\n")).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 2, "é")
+        );
+    }
+
+    /// Stepping through the minutes takes as long as the solutions say.
+    #[test]
+    fn expedition_finishes_in_time() {
+        let data = parse_input(Source::new("example.txt", include_str!("../example.txt")));
+        let data = data.unwrap();
+        for (trips, minutes) in [("1", 18), ("3", 54)] {
+            let mut session = Vec::new();
+            let commands = format!("trips {trips}\ngoto 1000\n");
+            repl(&data)
+                .run_with(commands.as_bytes(), &mut session)
+                .unwrap();
+            let session = String::from_utf8(session).unwrap();
+            assert!(
+                session.contains(&format!("step {minutes}, the last one")),
+                "{session}"
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::BufRead;
+
+use common::num::{ArithmeticError, Int};
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+fn parse_snafu<'a>(source: Source<'a>, x: &'a str) -> Result<&'a str, ParseError> {
+    let invalid_digit = x.char_indices().find(|(_, c)| !"=-012".contains(*c));
+    match invalid_digit {
+        Some((idx, c)) => Err(source.error_at(&x[idx..idx + c.len_utf8()], "a SNAFU digit")),
+        None if x.is_empty() => Err(source.error_at(x, "a SNAFU number")),
+        None => Ok(x),
+    }
+}
+
+#[aoc_parser(day = 25)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<&str>, ParseError> {
+    source.lines().map(|x| parse_snafu(source, x)).collect()
+}
+
+pub fn from_snafu<N: Int>(value: &str) -> Result<N, ArithmeticError> {
+    let five = N::from_i64(5);
+    let mut result = N::from_i64(0);
+    for c in value.chars() {
+        let digit = match c {
+            '=' => -2,
+            '-' => -1,
+            '0' => 0,
+            '1' => 1,
+            '2' => 2,
+            _ => unreachable!("{c}"),
+        };
+        result = result
+            .checked_mul(&five)?
+            .checked_add(&N::from_i64(digit))?;
+    }
+    Ok(result)
+}
+
+pub fn to_snafu<N: Int>(mut value: N) -> Result<String, ArithmeticError> {
+    let (two, five) = (N::from_i64(2), N::from_i64(5));
+    let mut result = vec![];
+
+    while !value.is_zero() {
+        value = value.checked_add(&two)?;
+        let remainder = value.checked_rem(&five)?.to_i64();
+        let next_char = match remainder {
+            Some(0) => '=',
+            Some(1) => '-',
+            Some(r @ 2..=4) => char::from_digit((r - 2) as u32, 10).unwrap(),
+            _ => unreachable!("{remainder:?}"),
+        };
+        result.push(next_char);
+        value = value.checked_div(&five)?;
+    }
+
+    Ok(result.iter().rev().join(""))
+}
+
+/// The sum of the numbers, in SNAFU.
+fn snafu_total<N: Int>(total: N) -> Result<String, ArithmeticError> {
+    let snafu = to_snafu(total.clone())?;
+    assert_eq!(total, from_snafu(&snafu)?);
+    Ok(snafu)
+}
+
+#[aoc(day = 25, part = 1, stream = stream_part1)]
+pub fn solve_part1<N: Int>(data: &[&str]) -> Result<String, ArithmeticError> {
+    let mut total = N::from_i64(0);
+    for number in data {
+        total = total.checked_add(&from_snafu(number)?)?;
+    }
+    snafu_total(total)
+}
+
+pub fn stream_part1<N: Int, R: BufRead>(lines: &mut LineReader<R>) -> Result<String, StreamError> {
+    let mut total = N::from_i64(0);
+    while let Some(line) = lines.next_line()? {
+        let number = from_snafu(parse_snafu(line, line.text())?)?;
+        total = total.checked_add(&number)?;
+    }
+
+    Ok(snafu_total(total)?)
+}
+
+#[aoc(day = 25, part = 2)]
+pub fn solve_part2(_data: &[&str]) -> String {
+    todo!()
+}
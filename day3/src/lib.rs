@@ -0,0 +1,188 @@
+use std::{collections::BTreeSet, io::BufRead};
+
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+fn priority(c: char) -> i64 {
+    let value = if c.is_ascii_lowercase() {
+        (c as u32) - ('a' as u32) + 1
+    } else {
+        (c as u32) - ('A' as u32) + 27
+    };
+    value as i64
+}
+
+fn parse_rucksack<'a>(source: Source<'a>, rucksack: &'a str) -> Result<&'a str, ParseError> {
+    if let Some((idx, _)) = rucksack
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_alphabetic())
+    {
+        Err(source.error_at(&rucksack[idx..], "an item letter"))
+    } else if rucksack.is_empty() {
+        Err(source.error_at(rucksack, "an item letter"))
+    } else if !rucksack.len().is_multiple_of(2) {
+        let end = &rucksack[rucksack.len()..];
+        Err(source.error_at(end, "an even number of items"))
+    } else if misplaced_item(rucksack).is_none() {
+        Err(source.error_at(rucksack, "an item in both compartments"))
+    } else {
+        Ok(rucksack)
+    }
+}
+
+#[aoc_parser(day = 3)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<&str>, ParseError> {
+    let rucksacks: Vec<&str> = source
+        .lines()
+        .map(|rucksack| parse_rucksack(source, rucksack))
+        .try_collect()?;
+    for group in rucksacks.chunks(3) {
+        match group {
+            [first, second, third] => {
+                if badge(&[first, second, third]).is_none() {
+                    return Err(source.error_at(third, "an item shared by the group of three"));
+                }
+            }
+            _ => {
+                let end = &source.text()[source.text().len()..];
+                return Err(source.error_at(end, "the rest of a group of three rucksacks"));
+            }
+        }
+    }
+    Ok(rucksacks)
+}
+
+/// The item that is in both compartments of the rucksack.
+fn misplaced_item(rucksack: &str) -> Option<char> {
+    let (front, back) = rucksack.split_at(rucksack.len() / 2);
+    let front_chars: BTreeSet<_> = front.chars().collect();
+    back.chars()
+        .find(|back_char| front_chars.contains(back_char))
+}
+
+/// The priority of the item that is in both compartments of the rucksack.
+fn misplaced_priority(rucksack: &str) -> i64 {
+    priority(misplaced_item(rucksack).expect("the parser checks for a misplaced item"))
+}
+
+/// The item that every rucksack in a group carries.
+fn badge(group: &[&str]) -> Option<char> {
+    let mut shared_items = group[0].chars().collect::<BTreeSet<_>>();
+    for rucksack in &group[1..] {
+        shared_items.retain(|item| rucksack.contains(*item));
+    }
+    shared_items.first().copied()
+}
+
+#[aoc(day = 3, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &[&str]) -> i64 {
+    data.iter().copied().map(misplaced_priority).sum()
+}
+
+#[aoc(day = 3, part = 2, stream = stream_part2)]
+pub fn solve_part2(data: &[&str]) -> i64 {
+    data.chunks(3)
+        .map(|group| priority(badge(group).expect("the parser checks for a badge")))
+        .sum()
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<i64, StreamError> {
+    let mut total = 0;
+    lines.try_for_each(|line| {
+        total += misplaced_priority(parse_rucksack(line, line.text())?);
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn stream_part2<R: BufRead>(lines: &mut LineReader<R>) -> Result<i64, StreamError> {
+    let mut total = 0;
+    let mut group_size = 0;
+    // The items carried by every elf in the current group so far.
+    let mut shared_items = BTreeSet::new();
+    // The error to report if the input ends partway through the current group.
+    let mut unfinished_group = None;
+    lines.try_for_each(|line| {
+        let items = parse_rucksack(line, line.text())?
+            .chars()
+            .collect::<BTreeSet<_>>();
+        shared_items = if group_size == 0 {
+            items
+        } else {
+            shared_items.intersection(&items).copied().collect()
+        };
+        group_size += 1;
+
+        if group_size == 3 {
+            let badge = shared_items.first().ok_or_else(|| {
+                line.error_at(line.text(), "an item shared by the group of three")
+            })?;
+            total += priority(*badge);
+            group_size = 0;
+            unfinished_group = None;
+        } else {
+            let end = &line.text()[line.text().len()..];
+            unfinished_group = Some(line.error_at(end, "the rest of a group of three rucksacks"));
+        }
+        Ok(())
+    })?;
+    match unfinished_group {
+        Some(error) => Err(error.into()),
+        None => Ok(total),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use common::parse::Source;
+    use common::stream::{LineReader, StreamError};
+
+    use super::{parse_input, stream_part1, stream_part2};
+
+    fn parse_error(text: &str) -> (usize, String) {
+        let error = parse_input(Source::new("input.txt", text)).unwrap_err();
+        (error.line, error.expected)
+    }
+
+    /// Inputs that the solutions have no answer for are rejected by the parser.
+    #[test]
+    fn rejects_rucksacks_without_an_answer() {
+        let group =
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n";
+        assert!(parse_input(Source::new("input.txt", group)).is_ok());
+
+        let expected = |line: usize, expected: &str| (line, expected.to_string());
+        assert_eq!(parse_error(""), expected(1, "an item letter"));
+        assert_eq!(parse_error("aa\n\nbb\n"), expected(2, "an item letter"));
+        assert_eq!(
+            parse_error("abcd\n"),
+            expected(1, "an item in both compartments")
+        );
+        assert_eq!(
+            parse_error("aa\nbb\ncc\n"),
+            expected(3, "an item shared by the group of three")
+        );
+        let unfinished = format!("{group}aa\n");
+        assert_eq!(
+            parse_error(&unfinished),
+            expected(5, "the rest of a group of three rucksacks")
+        );
+    }
+
+    #[test]
+    fn streaming_rejects_an_unfinished_group() {
+        let mut lines = LineReader::new("input.txt", Cursor::new("aa\naa\n"));
+        assert_eq!(stream_part1(&mut lines).unwrap(), 2);
+
+        let mut lines = LineReader::new("input.txt", Cursor::new("aa\naa\n"));
+        match stream_part2(&mut lines) {
+            Err(StreamError::Parse(error)) => assert_eq!(error.line, 2),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::{io::BufRead, ops::RangeInclusive};
+
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+fn parse_range(source: Source<'_>, range: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let (from, to) = source.split_once(range, "-")?;
+    Ok(source.parse_at(from, "a section ID")?..=source.parse_at(to, "a section ID")?)
+}
+
+pub type Assignments = (RangeInclusive<i64>, RangeInclusive<i64>);
+
+fn parse_assignments<'a>(source: Source<'a>, line: &'a str) -> Result<Assignments, ParseError> {
+    let (left, right) = source.split_once(line, ",")?;
+    Ok((parse_range(source, left)?, parse_range(source, right)?))
+}
+
+#[aoc_parser(day = 4)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Assignments>, ParseError> {
+    source
+        .lines()
+        .map(|x| parse_assignments(source, x))
+        .collect()
+}
+
+fn fully_contains(bigger: &RangeInclusive<i64>, smaller: &RangeInclusive<i64>) -> bool {
+    bigger.start() <= smaller.start() && bigger.end() >= smaller.end()
+}
+
+fn intersect(left: &RangeInclusive<i64>, right: &RangeInclusive<i64>) -> bool {
+    let larger_start = std::cmp::max(left.start(), right.start());
+    let smaller_end = std::cmp::min(left.end(), right.end());
+
+    larger_start <= smaller_end
+}
+
+#[aoc(day = 4, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &[(RangeInclusive<i64>, RangeInclusive<i64>)]) -> usize {
+    data.iter()
+        .filter(|(left, right)| fully_contains(left, right) || fully_contains(right, left))
+        .count()
+}
+
+#[aoc(day = 4, part = 2, stream = stream_part2)]
+pub fn solve_part2(data: &[(RangeInclusive<i64>, RangeInclusive<i64>)]) -> usize {
+    data.iter()
+        .filter(|(left, right)| intersect(left, right))
+        .count()
+}
+
+/// Count the pairs that match the condition as they are read, without keeping them.
+fn stream_count<R: BufRead>(
+    lines: &mut LineReader<R>,
+    condition: impl Fn(&RangeInclusive<i64>, &RangeInclusive<i64>) -> bool,
+) -> Result<usize, StreamError> {
+    let mut count = 0;
+    lines.try_for_each(|line| {
+        let (left, right) = parse_assignments(line, line.text())?;
+        if condition(&left, &right) {
+            count += 1;
+        }
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<usize, StreamError> {
+    stream_count(lines, |left, right| {
+        fully_contains(left, right) || fully_contains(right, left)
+    })
+}
+
+pub fn stream_part2<R: BufRead>(lines: &mut LineReader<R>) -> Result<usize, StreamError> {
+    stream_count(lines, intersect)
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use common::parse::Source;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Err(e) = day5::parse_input(Source::new("fuzz", text)) {
+            // Rendering the error must not panic either.
+            let _ = e.to_string();
+        }
+    }
+});
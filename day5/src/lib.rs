@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+
+use common::input;
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::timeline::{self, Simulation, Timeline};
+use common::visualize::Frames;
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+use tracing::debug;
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Instruction {
+    pub fn parse(source: Source<'_>, line: &str, stack_count: usize) -> Result<Self, ParseError> {
+        let components = line.split(' ').collect_vec();
+        let component = |idx: usize| components.get(idx).copied().unwrap_or(&line[line.len()..]);
+        let stack_number = |idx: usize| {
+            let expected = format!("a stack number between 1 and {stack_count}");
+            let number: usize = source.parse_at(component(idx), &expected)?;
+            if (1..=stack_count).contains(&number) {
+                Ok(number - 1)
+            } else {
+                Err(source.error_at(component(idx), expected))
+            }
+        };
+
+        source.one_of(component(0), &["move"])?;
+        let count = source.parse_at(component(1), "a crate count")?;
+        source.one_of(component(2), &["from"])?;
+        let from = stack_number(3)?;
+        source.one_of(component(4), &["to"])?;
+        let to = stack_number(5)?;
+        if components.len() > 6 {
+            return Err(source.error_at(component(6), "end of line"));
+        }
+
+        Ok(Instruction { count, from, to })
+    }
+}
+
+/// The starting stacks, bottom first, and the moves to make with them.
+pub type Procedure = (Vec<VecDeque<char>>, Vec<Instruction>);
+
+#[aoc_parser(day = 5)]
+pub fn parse_input(source: Source<'_>) -> Result<Procedure, ParseError> {
+    let mut paragraphs = source.paragraphs();
+    let stacks = paragraphs.next().unwrap();
+    let Some(instructions) = paragraphs.next() else {
+        return Err(source.error_at(&stacks[stacks.len()..], "a blank line and the moves"));
+    };
+    if let Some(extra) = paragraphs.next() {
+        return Err(source.error_at(extra, "the end of the input"));
+    }
+    let mut stack_lines: Vec<_> = input::lines(stacks).collect();
+    let stack_ids = stack_lines.pop().unwrap();
+
+    let stack_indices = stack_ids
+        .char_indices()
+        .filter_map(|(idx, c)| c.is_ascii_digit().then_some(idx))
+        .collect_vec();
+    if stack_indices.is_empty() {
+        return Err(source.error_at(stack_ids, "a line of stack numbers"));
+    }
+
+    let mut stacks: Vec<VecDeque<char>> = Vec::with_capacity(stack_indices.len());
+    stacks.resize(stack_indices.len(), Default::default());
+
+    for line in stack_lines {
+        for (idx, offset) in stack_indices.iter().enumerate() {
+            // Lines have no trailing whitespace, so they stop after the last crate on them.
+            let slot = line
+                .get(*offset..)
+                .and_then(|rest| rest.chars().next())
+                .unwrap_or(' ');
+            if slot == '[' || slot == ']' {
+                return Err(source.error_at(&line[*offset..], "a crate or a space"));
+            } else if slot != ' ' {
+                stacks.get_mut(idx).unwrap().push_back(slot);
+            }
+        }
+    }
+
+    let instructions = input::lines(instructions)
+        .map(|line| Instruction::parse(source, line, stacks.len()))
+        .collect::<Result<_, _>>()?;
+
+    Ok((stacks, instructions))
+}
+
+/// Draw the stacks the way the puzzle input does, with the top crates at the top.
+fn render_stacks(stacks: &[VecDeque<char>]) -> String {
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or_default();
+    let mut result = String::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|s| match s.len().checked_sub(level + 1).map(|idx| s[idx]) {
+                Some(container) => format!("[{container}]"),
+                None => "   ".to_string(),
+            })
+            .join(" ");
+        result += row.trim_end();
+        result += "\n";
+    }
+    result += &(1..=stacks.len()).map(|idx| format!(" {idx} ")).join(" ");
+    result += "\n";
+    result
+}
+
+/// The crane that rearranges the crates: part 1's CrateMover 9000 moves them one at a time,
+/// and part 2's CrateMover 9001 moves several at once, keeping their order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrateMover {
+    Model9000,
+    Model9001,
+}
+
+/// The stacks partway through the rearrangement, and the moves still to make.
+#[derive(Debug, Clone)]
+pub struct Crane<'a> {
+    stacks: Vec<VecDeque<char>>,
+    instructions: &'a [Instruction],
+    next: usize,
+    model: CrateMover,
+}
+
+impl<'a> Crane<'a> {
+    pub fn new(
+        stacks: Vec<VecDeque<char>>,
+        instructions: &'a [Instruction],
+        model: CrateMover,
+    ) -> Self {
+        Self {
+            stacks,
+            instructions,
+            next: 0,
+            model,
+        }
+    }
+
+    /// The crate on top of each stack.
+    fn top_crates(&self) -> String {
+        self.stacks.iter().map(|s| s.front().unwrap()).collect()
+    }
+}
+
+impl Simulation for Crane<'_> {
+    /// Make the next move.
+    fn step(&mut self) -> bool {
+        let Some(instr) = self.instructions.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+        debug!(
+            count = instr.count,
+            from = instr.from,
+            to = instr.to,
+            "moving containers"
+        );
+        let stacks = &mut self.stacks;
+        match self.model {
+            CrateMover::Model9000 => {
+                for _ in 0..instr.count {
+                    let container = stacks.get_mut(instr.from).unwrap().pop_front().unwrap();
+                    stacks.get_mut(instr.to).unwrap().push_front(container);
+                }
+            }
+            CrateMover::Model9001 => {
+                let from = stacks.get_mut(instr.from).unwrap();
+                let containers = (0..instr.count)
+                    .map(|_| from.pop_front().unwrap())
+                    .collect_vec();
+
+                let to = stacks.get_mut(instr.to).unwrap();
+                for container in containers.into_iter().rev() {
+                    to.push_front(container);
+                }
+            }
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        render_stacks(&self.stacks)
+    }
+}
+
+fn rearrange(mut crane: Crane<'_>, frames: &mut Frames) -> String {
+    frames.push(|| crane.render());
+    while crane.step() {
+        frames.push(|| crane.render());
+    }
+    frames.finish(|| crane.render());
+
+    crane.top_crates()
+}
+
+#[aoc(day = 5, part = 1)]
+pub fn solve_part1((stacks, instructions): Procedure, frames: &mut Frames) -> String {
+    rearrange(
+        Crane::new(stacks, &instructions, CrateMover::Model9000),
+        frames,
+    )
+}
+
+#[aoc(day = 5, part = 2)]
+pub fn solve_part2((stacks, instructions): Procedure, frames: &mut Frames) -> String {
+    rearrange(
+        Crane::new(stacks, &instructions, CrateMover::Model9001),
+        frames,
+    )
+}
+
+/// Commands for stepping through the moves, with part 1's crane until told otherwise.
+pub fn repl((stacks, instructions): &Procedure) -> Repl<'_, Timeline<Crane<'_>>> {
+    let crane = move |model| Crane::new(stacks.to_vec(), instructions, model);
+    timeline::repl(5, crane(CrateMover::Model9000)).command(
+        "crane",
+        "<9000|9001>",
+        "start again with the other crane",
+        move |timeline, args| {
+            let model = match repl::arg(args, 0, "a crane model")? {
+                9000 => CrateMover::Model9000,
+                9001 => CrateMover::Model9001,
+                model => return Err(format!("expected 9000 or 9001, found {model}")),
+            };
+            *timeline = Timeline::new(crane(model));
+            Ok(format!("started again with the CrateMover {}", args[0]))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse::Source;
+    use common::visualize::Frames;
+
+    use super::{parse_input, solve_part1};
+
+    #[test]
+    fn renders_the_example_stacks() {
+        let source = Source::new("example.txt", include_str!("../example.txt"));
+        let procedure = parse_input(source).unwrap();
+        let mut frames = Frames::final_only();
+        solve_part1(procedure, &mut frames);
+        insta::assert_snapshot!(frames.frames().last().unwrap());
+    }
+}
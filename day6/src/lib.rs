@@ -0,0 +1,109 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    io::BufRead,
+};
+
+use common::parse::{ParseError, Source};
+use common::stream::{LineReader, StreamError};
+use common::{aoc, aoc_parser};
+use itertools::process_results;
+
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 6)]
+pub fn parse_input(source: Source<'_>) -> Result<&str, ParseError> {
+    Ok(source.text().trim_end())
+}
+
+#[aoc(day = 6, part = 1, stream = stream_part1)]
+pub fn solve_part1(data: &str) -> usize {
+    data.char_indices()
+        .tuple_windows()
+        .find_map(|((_, a), (_, b), (_, c), (idx, d))| {
+            let chars = [a, b, c, d];
+            (chars.into_iter().collect::<BTreeSet<_>>().len() == 4).then_some(idx + 1)
+        })
+        .expect("no solution found")
+}
+
+#[aoc(day = 6, part = 2, params(marker_length = 14), stream = stream_part2)]
+pub fn solve_part2(data: &str, marker_length: usize) -> usize {
+    find_marker(data.chars(), marker_length).expect("no solution found")
+}
+
+pub fn stream_part1<R: BufRead>(lines: &mut LineReader<R>) -> Result<usize, StreamError> {
+    let marker = process_results(lines.line_chars(), |chars| find_marker(chars, 4))?;
+    Ok(marker.expect("no solution found"))
+}
+
+pub fn stream_part2<R: BufRead>(
+    lines: &mut LineReader<R>,
+    marker_length: usize,
+) -> Result<usize, StreamError> {
+    let marker = process_results(lines.line_chars(), |chars| {
+        find_marker(chars, marker_length)
+    })?;
+    Ok(marker.expect("no solution found"))
+}
+
+/// Find the number of characters up to and including the first `length` consecutive
+/// distinct characters, by sliding a window of character counts along the datastream.
+/// Only the last `length` characters are kept.
+fn find_marker(data: impl IntoIterator<Item = char>, length: usize) -> Option<usize> {
+    let mut recent = VecDeque::with_capacity(length + 1);
+    let mut window: BTreeMap<char, usize> = Default::default();
+
+    for (idx, c) in data.into_iter().enumerate() {
+        recent.push_back(c);
+        *window.entry(c).or_default() += 1;
+
+        if recent.len() > length {
+            let evicted_char = recent.pop_front().unwrap();
+            let removed_count = window.get_mut(&evicted_char).unwrap();
+            if *removed_count == 1 {
+                window.remove(&evicted_char);
+            } else {
+                *removed_count -= 1;
+            }
+        }
+
+        if window.len() == length {
+            return Some(idx + 1);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::{find_marker, solve_part1};
+
+    /// Check every window from scratch, building a new set for each one.
+    fn reference_marker(data: &str, length: usize) -> Option<usize> {
+        let chars: Vec<char> = data.chars().collect();
+        chars
+            .windows(length)
+            .position(|window| window.iter().collect::<BTreeSet<_>>().len() == length)
+            .map(|idx| idx + length)
+    }
+
+    proptest! {
+        #[test]
+        fn sliding_window_matches_reference(data in "[a-h]{0,40}", length in 1usize..8) {
+            prop_assert_eq!(find_marker(data.chars(), length), reference_marker(&data, length));
+        }
+
+        #[test]
+        fn part1_matches_reference(data in "[a-f]{0,40}") {
+            if let Some(expected) = reference_marker(&data, 4) {
+                prop_assert_eq!(solve_part1(&data), expected);
+            }
+        }
+    }
+}
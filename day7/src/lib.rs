@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, line_ending, space0, space1},
+    combinator::{eof, map},
+    error::ErrorKind,
+    multi::{many0, many1},
+    sequence::{delimited, preceded, separated_pair, terminated},
+    IResult, InputTakeAtPosition,
+};
+
+use common::parse::{ParseError, Source};
+use common::repl::{self, Repl};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    ChangeDirectoryToRoot,
+    ChangeDirectoryUpOne,
+    ChangeDirectoryInto(Rc<str>),
+    List,
+}
+
+#[derive(Debug, Clone)]
+pub enum DirEntry {
+    File(Rc<str>, u64),
+    Directory(Rc<str>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandAndOutput {
+    pub cmd: Command,
+    pub output: Vec<DirEntry>,
+}
+
+fn cd_command(input: &str) -> IResult<&str, CommandAndOutput> {
+    delimited(
+        terminated(tag("cd"), space1),
+        map(
+            alt((
+                map(tag("/"), |_| Command::ChangeDirectoryToRoot),
+                map(tag(".."), |_| Command::ChangeDirectoryUpOne),
+                map(alphanumeric1, |name: &str| {
+                    Command::ChangeDirectoryInto(Rc::from(name))
+                }),
+            )),
+            |cmd| CommandAndOutput {
+                cmd,
+                output: vec![],
+            },
+        ),
+        terminated(space0, end_of_line),
+    )(input)
+}
+
+/// The end of a line, which may be the end of the input if the last line has no line ending.
+fn end_of_line(input: &str) -> IResult<&str, &str> {
+    alt((line_ending, eof))(input)
+}
+
+fn filename(input: &str) -> IResult<&str, &str> {
+    input.split_at_position1_complete(
+        |item| !(item.is_alphanumeric() || item == '.' || item == '_'),
+        ErrorKind::AlphaNumeric,
+    )
+}
+
+fn dir_entry(input: &str) -> IResult<&str, DirEntry> {
+    terminated(
+        alt((
+            preceded(
+                terminated(tag("dir"), space1),
+                map(filename, |name: &str| DirEntry::Directory(Rc::from(name))),
+            ),
+            map(
+                separated_pair(
+                    nom::character::complete::u64::<&str, nom::error::Error<&str>>,
+                    space1,
+                    filename,
+                ),
+                |(size, name)| DirEntry::File(Rc::from(name), size),
+            ),
+        )),
+        terminated(space0, end_of_line),
+    )(input)
+}
+
+fn ls_command(input: &str) -> IResult<&str, CommandAndOutput> {
+    preceded(
+        terminated(tag("ls"), terminated(space0, end_of_line)),
+        map(many0(dir_entry), |output| CommandAndOutput {
+            cmd: Command::List,
+            output,
+        }),
+    )(input)
+}
+
+fn executed_command(input: &str) -> IResult<&str, CommandAndOutput> {
+    preceded(terminated(tag("$"), space1), alt((cd_command, ls_command)))(input)
+}
+
+#[aoc_parser(day = 7)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<CommandAndOutput>, ParseError> {
+    match many1(executed_command)(source.text().trim_end()) {
+        Ok(("", commands)) => Ok(commands),
+        Ok((leftovers, _)) => Err(source.error_at(
+            leftovers,
+            "a `$ cd` or `$ ls` command, or a directory entry",
+        )),
+        Err(e) => Err(source.nom_error(e)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Path(Vec<Rc<str>>);
+
+impl Path {
+    /// Parse an absolute path like `/a/e`.
+    fn parse(path: &str) -> Option<Self> {
+        let rest = path.strip_prefix('/')?;
+        let components = rest.split('/').filter(|c| !c.is_empty()).map(Rc::from);
+        Some(Self(
+            std::iter::once(Rc::from("/")).chain(components).collect(),
+        ))
+    }
+
+    fn dir(&self, dir_name: &Rc<str>) -> Self {
+        let mut components = self.0.clone();
+        components.push(dir_name.clone());
+        Self(components)
+    }
+}
+
+fn construct_filesystem(data: &[CommandAndOutput]) -> BTreeMap<Path, Vec<DirEntry>> {
+    let root_dir: Rc<str> = Rc::from("/");
+    let mut fs: BTreeMap<Path, Vec<DirEntry>> = Default::default();
+
+    assert_eq!(
+        data.first().expect("empty data").cmd,
+        Command::ChangeDirectoryToRoot
+    );
+    let mut current_path = vec![root_dir.clone()];
+
+    for cmd_and_output in data {
+        match &cmd_and_output.cmd {
+            Command::ChangeDirectoryToRoot => current_path = vec![root_dir.clone()],
+            Command::ChangeDirectoryUpOne => {
+                current_path.pop();
+            }
+            Command::ChangeDirectoryInto(dir) => current_path.push(dir.clone()),
+            Command::List => {
+                fs.insert(Path(current_path.clone()), cmd_and_output.output.clone());
+            }
+        }
+    }
+
+    fs
+}
+
+fn compute_directory_size(
+    path: &Path,
+    fs: &BTreeMap<Path, Vec<DirEntry>>,
+    size_cache: &mut BTreeMap<Path, u64>,
+) -> u64 {
+    if let Some(size) = size_cache.get(path) {
+        return *size;
+    }
+
+    let mut size = 0;
+    if let Some(entries) = fs.get(path) {
+        for entry in entries {
+            match entry {
+                DirEntry::File(_, file_size) => size += *file_size,
+                DirEntry::Directory(dir_name) => {
+                    size += compute_directory_size(&path.dir(dir_name), fs, size_cache);
+                }
+            }
+        }
+    }
+
+    size_cache.insert(path.clone(), size);
+
+    size
+}
+
+#[aoc(day = 7, part = 1, params(max_size = 100000))]
+pub fn solve_part1(data: &[CommandAndOutput], max_size: u64) -> u64 {
+    let fs = construct_filesystem(data);
+    let mut size_cache = Default::default();
+    let fs_root = Path(vec![Rc::from("/")]);
+
+    compute_directory_size(&fs_root, &fs, &mut size_cache);
+
+    size_cache
+        .values()
+        .filter_map(|size| (size <= &max_size).then_some(*size))
+        .sum()
+}
+
+#[aoc(
+    day = 7,
+    part = 2,
+    params(full_space = 70000000, required_free_space = 30000000)
+)]
+pub fn solve_part2(data: &[CommandAndOutput], full_space: i64, required_free_space: i64) -> u64 {
+    let fs = construct_filesystem(data);
+    let mut size_cache = Default::default();
+    let fs_root = Path(vec![Rc::from("/")]);
+
+    compute_directory_size(&fs_root, &fs, &mut size_cache);
+
+    let total_size = size_cache[&fs_root] as i64;
+    let free_space = full_space - total_size;
+    assert!(free_space >= 0, "the total size of the filesystem {total_size} is larger than the available capacity {full_space}");
+
+    let size_to_free_up = required_free_space - free_space;
+    assert!(
+        size_to_free_up >= 0,
+        "sufficient free space is already available"
+    );
+
+    size_cache
+        .values()
+        .filter_map(|size| (size >= &(size_to_free_up as u64)).then_some(*size))
+        .min()
+        .expect("no directory is big enough to free up the needed space")
+}
+
+/// The filesystem seen in the terminal output, and the sizes of the directories worked out
+/// so far.
+pub struct Filesystem {
+    dirs: BTreeMap<Path, Vec<DirEntry>>,
+    size_cache: BTreeMap<Path, u64>,
+}
+
+impl Filesystem {
+    fn listed_dir(&self, args: &[&str]) -> Result<Path, String> {
+        let arg: String = repl::arg(args, 0, "an absolute path")?;
+        let path = Path::parse(&arg).ok_or_else(|| format!("`{arg}` isn't an absolute path"))?;
+        if !self.dirs.contains_key(&path) {
+            return Err(format!("`{arg}` was never listed"));
+        }
+        Ok(path)
+    }
+}
+
+/// Commands for exploring the filesystem.
+pub fn repl(data: &[CommandAndOutput]) -> Repl<'static, Filesystem> {
+    let fs = Filesystem {
+        dirs: construct_filesystem(data),
+        size_cache: BTreeMap::new(),
+    };
+    Repl::new(7, fs)
+        .command("ls", "<path>", "list a directory", |fs, args| {
+            let path = fs.listed_dir(args)?;
+            let mut entries = fs.dirs[&path].iter().map(|entry| match entry {
+                DirEntry::File(name, size) => format!("{size} {name}"),
+                DirEntry::Directory(name) => format!("dir {name}"),
+            });
+            Ok(entries.join("\n"))
+        })
+        .command(
+            "size",
+            "<path>",
+            "the total size of a directory",
+            |fs, args| {
+                let path = fs.listed_dir(args)?;
+                Ok(compute_directory_size(&path, &fs.dirs, &mut fs.size_cache).to_string())
+            },
+        )
+}
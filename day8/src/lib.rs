@@ -0,0 +1,239 @@
+use std::{cmp::max, io};
+
+use common::image::{self, PngExport};
+use common::parse::{ParseError, Source};
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+
+#[aoc_parser(day = 8)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<Vec<i64>>, ParseError> {
+    let mut width = None;
+    source
+        .lines()
+        .map(|x| {
+            let row = x
+                .char_indices()
+                .map(|(idx, c)| {
+                    c.to_digit(10).map(|height| height as i64).ok_or_else(|| {
+                        source.error_at(&x[idx..idx + c.len_utf8()], "a tree height digit")
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let width = *width.get_or_insert(row.len());
+            if row.is_empty() {
+                return Err(source.error_at(x, "a tree height digit"));
+            }
+            if row.len() != width {
+                let position = &x[width.min(x.len())..];
+                return Err(source.error_at(position, format!("a row {width} trees wide")));
+            }
+            Ok(row)
+        })
+        .collect()
+}
+
+fn offset_coords(
+    x: usize,
+    y: usize,
+    dx: i64,
+    dy: i64,
+    max_x: usize,
+    max_y: usize,
+) -> Option<(usize, usize)> {
+    let new_x = (x as i64) - dx;
+    let new_y = (y as i64) - dy;
+
+    if new_x >= 0 && new_y >= 0 {
+        let new_x = new_x as usize;
+        let new_y = new_y as usize;
+        if new_x < max_x && new_y < max_y {
+            return Some((new_x, new_y));
+        }
+    }
+
+    None
+}
+
+fn calculate_directional_visibility_minimum(data: &[Vec<i64>], dx: i64, dy: i64) -> Vec<Vec<i64>> {
+    let max_x = data.len();
+    let max_y = data[0].len();
+
+    let mut result = Vec::with_capacity(max_x);
+    let empty_vec = vec![-1; max_y];
+    result.resize(max_x, empty_vec);
+
+    let x_indices = if dx > 0 {
+        (0..max_x).collect_vec()
+    } else {
+        (0..max_x).rev().collect_vec()
+    };
+    let y_indices = if dy > 0 {
+        (0..max_y).collect_vec()
+    } else {
+        (0..max_y).rev().collect_vec()
+    };
+
+    for x in x_indices.iter().copied() {
+        for y in y_indices.iter().copied() {
+            if let Some((new_x, new_y)) = offset_coords(x, y, dx, dy, max_x, max_y) {
+                result[x][y] = max(result[new_x][new_y], data[new_x][new_y]);
+            }
+        }
+    }
+
+    result
+}
+
+#[aoc(day = 8, part = 1)]
+pub fn solve_part1(data: &[Vec<i64>]) -> usize {
+    let deltas = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+    let visibilities = deltas
+        .iter()
+        .copied()
+        .map(|(dx, dy)| calculate_directional_visibility_minimum(data, dx, dy))
+        .collect_vec();
+
+    data.iter()
+        .enumerate()
+        .map(|(x, row)| {
+            row.iter()
+                .enumerate()
+                .filter_map(|(y, value)| visibilities.iter().find(|vis| vis[x][y] < *value))
+                .count()
+        })
+        .sum()
+}
+
+#[aoc(day = 8, part = 2)]
+pub fn solve_part2(data: &[Vec<i64>]) -> usize {
+    scenic_scores(data).into_iter().flatten().max().unwrap()
+}
+
+/// Draw every tree's scenic score, as a heat map.
+pub fn draw_scenic_scores(data: &[Vec<i64>], png: &PngExport) -> io::Result<()> {
+    // Scenic scores are products, so they're coloured on a log scale.
+    let scores = scenic_scores(data);
+    let max_score = scores.iter().flatten().copied().max().unwrap_or_default();
+    let scale = ((max_score + 1) as f64).ln().max(f64::EPSILON);
+    png.write(&scores, |score| {
+        image::heat(((score + 1) as f64).ln() / scale)
+    })
+}
+
+pub fn scenic_scores(data: &[Vec<i64>]) -> Vec<Vec<usize>> {
+    let max_x = data.len();
+    let max_y = data[0].len();
+
+    let deltas = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+    data.iter()
+        .enumerate()
+        .map(|(x, vals)| {
+            vals.iter()
+                .enumerate()
+                .map(|(y, height)| {
+                    let (scenic_1, scenic_2, scenic_3, scenic_4) = (0..4)
+                        .map(|idx| {
+                            let (dx, dy) = deltas[idx];
+
+                            let mut count = 0;
+                            let (mut cur_x, mut cur_y) = (x, y);
+                            while let Some((next_x, next_y)) =
+                                offset_coords(cur_x, cur_y, dx, dy, max_x, max_y)
+                            {
+                                count += 1;
+                                if data[next_x][next_y] >= *height {
+                                    break;
+                                }
+
+                                cur_x = next_x;
+                                cur_y = next_y;
+                            }
+
+                            count
+                        })
+                        .collect_tuple()
+                        .unwrap();
+
+                    scenic_1 * scenic_2 * scenic_3 * scenic_4
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use common::parse::Source;
+
+    use super::{calculate_directional_visibility_minimum, parse_input, solve_part1};
+
+    const DELTAS: [(i64, i64); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+    /// The tallest tree seen by walking from `(x, y)` in steps of `(-dx, -dy)`, or -1 if none.
+    fn reference_tallest_behind(data: &[Vec<i64>], x: usize, y: usize, dx: i64, dy: i64) -> i64 {
+        let (mut x, mut y) = (x as i64 - dx, y as i64 - dy);
+        let mut tallest = -1;
+        while x >= 0 && y >= 0 && (x as usize) < data.len() && (y as usize) < data[0].len() {
+            tallest = tallest.max(data[x as usize][y as usize]);
+            x -= dx;
+            y -= dy;
+        }
+        tallest
+    }
+
+    fn grid() -> impl Strategy<Value = Vec<Vec<i64>>> {
+        (1usize..8, 1usize..8).prop_flat_map(|(rows, columns)| {
+            prop::collection::vec(prop::collection::vec(0i64..10, columns), rows)
+        })
+    }
+
+    /// Grids that the solutions can't index into are rejected rather than panicking later.
+    #[test]
+    fn rejects_malformed_grids() {
+        let error = |text| parse_input(Source::new("input.txt", text)).unwrap_err();
+
+        let non_ascii = error("1é\n");
+        assert_eq!((non_ascii.column, non_ascii.found.as_str()), (2, "é"));
+
+        let ragged = error("123\n45\n678\n");
+        assert_eq!((ragged.line, ragged.column), (2, 3));
+        assert_eq!(ragged.expected, "a row 3 trees wide");
+        assert_eq!(error("123\n4567\n").found, "7");
+
+        assert_eq!(error("").expected, "a tree height digit");
+    }
+
+    proptest! {
+        #[test]
+        fn visibility_minimum_matches_naive_scan(data in grid()) {
+            for (dx, dy) in DELTAS {
+                let visibility = calculate_directional_visibility_minimum(&data, dx, dy);
+                for (x, row) in visibility.iter().enumerate() {
+                    for (y, tallest) in row.iter().enumerate() {
+                        let expected = reference_tallest_behind(&data, x, y, dx, dy);
+                        prop_assert_eq!(*tallest, expected, "at {:?} for {:?}", (x, y), (dx, dy));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn part1_matches_naive_scan(data in grid()) {
+            let mut expected = 0;
+            for (x, row) in data.iter().enumerate() {
+                for (y, height) in row.iter().enumerate() {
+                    let visible = DELTAS.iter().any(|(dx, dy)| {
+                        reference_tallest_behind(&data, x, y, *dx, *dy) < *height
+                    });
+                    expected += usize::from(visible);
+                }
+            }
+            prop_assert_eq!(solve_part1(&data), expected);
+        }
+    }
+}
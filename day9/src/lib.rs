@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeSet,
+    ops::{Add, Neg, Sub},
+};
+
+use common::parse::{ParseError, Source};
+use common::visualize::Frames;
+use common::{aoc, aoc_parser};
+#[allow(unused_imports)]
+use itertools::Itertools;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Direction {
+    Up = 0,
+    Down = 1,
+    Left = 2,
+    Right = 3,
+}
+
+impl Direction {
+    const ALL_UNIT_VECTORS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    fn unit_vector(&self) -> Vector2D {
+        // SAFETY: Because `Self` is marked `repr(u8)`, its layout is a `repr(C)` `union`
+        // between `repr(C)` structs, each of which has the `u8` discriminant as its first
+        // field, so we can read the discriminant without offsetting the pointer.
+        let idx = unsafe { *<*const _>::from(self).cast::<u8>() } as usize;
+        let (x, y) = Direction::ALL_UNIT_VECTORS[idx];
+        Vector2D::new(x, y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vector2D {
+    x: i64,
+    y: i64,
+}
+
+impl Vector2D {
+    fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    fn zero() -> Self {
+        Self { x: 0, y: 0 }
+    }
+}
+
+impl Add<Vector2D> for Vector2D {
+    type Output = Vector2D;
+
+    fn add(self, rhs: Vector2D) -> Self::Output {
+        Vector2D {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Neg for Vector2D {
+    type Output = Vector2D;
+
+    fn neg(self) -> Self::Output {
+        Vector2D {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Sub<Vector2D> for Vector2D {
+    type Output = Vector2D;
+
+    fn sub(self, rhs: Vector2D) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+fn move_toward_head(head: Vector2D, tail: Vector2D) -> Vector2D {
+    let delta = head - tail;
+    let signum = Vector2D {
+        x: delta.x.signum(),
+        y: delta.y.signum(),
+    };
+
+    if signum == delta {
+        // No move is necessary.
+        Vector2D::zero()
+    } else {
+        signum
+    }
+}
+
+#[aoc_parser(day = 9)]
+pub fn parse_input(source: Source<'_>) -> Result<Vec<(Direction, usize)>, ParseError> {
+    source
+        .lines()
+        .map(|x| {
+            let (direction, count) = source.split_once(x, " ")?;
+            let direction = match source.one_of(direction, &["U", "D", "L", "R"])? {
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "R" => Direction::Right,
+                _ => unreachable!(),
+            };
+
+            Ok((direction, source.parse_at(count, "a step count")?))
+        })
+        .collect()
+}
+
+/// Draw the area around the head of the rope, with the knots numbered from the head
+/// and the places the tail has visited marked with `#`.
+fn render_rope(rope: &[Vector2D], visited: &BTreeSet<Vector2D>) -> String {
+    const HALF_HEIGHT: i64 = 12;
+    const HALF_WIDTH: i64 = 40;
+
+    let head = rope[0];
+    let mut result = String::new();
+    for x in (head.x - HALF_HEIGHT)..=(head.x + HALF_HEIGHT) {
+        for y in (head.y - HALF_WIDTH)..=(head.y + HALF_WIDTH) {
+            let point = Vector2D::new(x, y);
+            let tile = match rope.iter().position(|knot| *knot == point) {
+                Some(0) => 'H',
+                Some(_) if rope.len() == 2 => 'T',
+                Some(idx) => char::from_digit((idx % 10) as u32, 10).unwrap(),
+                None if point == Vector2D::zero() => 's',
+                None if visited.contains(&point) => '#',
+                None => '.',
+            };
+            result.push(tile);
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[aoc(day = 9, part = 1)]
+pub fn solve_part1(data: &[(Direction, usize)], frames: &mut Frames) -> usize {
+    let mut visited: BTreeSet<Vector2D> = Default::default();
+
+    let mut head = Vector2D::zero();
+    let mut tail = Vector2D::zero();
+    visited.insert(tail);
+
+    for (direction, count) in data {
+        let unit_vector = direction.unit_vector();
+        for _ in 0..*count {
+            head = head + unit_vector;
+            tail = tail + move_toward_head(head, tail);
+            visited.insert(tail);
+            frames.push(|| render_rope(&[head, tail], &visited));
+        }
+        debug!(?direction, count, ?head, ?tail, "applied motion");
+    }
+    frames.finish(|| render_rope(&[head, tail], &visited));
+
+    visited.len()
+}
+
+#[aoc(day = 9, part = 2, params(rope_length = 10))]
+pub fn solve_part2(data: &[(Direction, usize)], rope_length: usize, frames: &mut Frames) -> usize {
+    let mut visited: BTreeSet<Vector2D> = Default::default();
+
+    let mut rope = vec![Vector2D::zero(); rope_length];
+    visited.insert(*rope.last().unwrap());
+
+    for (direction, count) in data {
+        let unit_vector = direction.unit_vector();
+        for _ in 0..*count {
+            rope[0] = rope[0] + unit_vector;
+            for idx in 1..rope.len() {
+                let child_move = move_toward_head(rope[idx - 1], rope[idx]);
+                rope[idx] = rope[idx] + child_move;
+            }
+            visited.insert(*rope.last().unwrap());
+            frames.push(|| render_rope(&rope, &visited));
+        }
+        debug!(?direction, count, head = ?rope[0], tail = ?rope.last().unwrap(), "applied motion");
+    }
+    frames.finish(|| render_rope(&rope, &visited));
+
+    visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse::Source;
+    use common::visualize::Frames;
+
+    use super::{parse_input, solve_part2};
+
+    #[test]
+    fn renders_the_larger_example() {
+        let data = parse_input(Source::new("example2.txt", include_str!("../example2.txt")));
+        let mut frames = Frames::final_only();
+        solve_part2(&data.unwrap(), 10, &mut frames);
+        insta::assert_snapshot!(frames.frames().last().unwrap());
+    }
+}
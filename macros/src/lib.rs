@@ -0,0 +1,298 @@
+//! Attributes for declaring a day's parser and solutions, so that its command line, and the
+//! hooks for benchmarking it, are generated instead of written out by hand for every day.
+//!
+//! They're re-exported by `common` with its `macros` feature, and both go in the day's
+//! `lib.rs`, next to each other:
+//!
+//! ```ignore
+//! #[aoc_parser(day = 16)]
+//! pub fn parse_input(source: Source<'_>) -> Result<Vec<Valve>, ParseError> { ... }
+//!
+//! #[aoc(day = 16, part = 1, params(minutes = 30))]
+//! pub fn solve_part1(data: &[Valve], max_time: usize) -> i64 { ... }
+//! ```
+//!
+//! Then `common::aoc_main!(day16);` in `main.rs` is the whole binary.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, spanned::Spanned, Error, Expr, FnArg, Ident, ItemFn,
+    LitInt, Path, ReturnType, Type,
+};
+
+/// Mark the function that parses a day's input. It takes a `Source` and returns a
+/// `Result<_, ParseError>`, and the solutions get what it returns.
+#[proc_macro_attribute]
+pub fn aoc_parser(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut day = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("day") {
+            day = Some(meta.value()?.parse::<LitInt>()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `day`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    let Some(day) = day else {
+        return missing("day").into_compile_error().into();
+    };
+    let name = &function.sig.ident;
+    quote! {
+        #function
+
+        #[doc(hidden)]
+        pub use self::#name as __aoc_parse;
+
+        #[doc(hidden)]
+        pub const __AOC_DAY: u32 = #day;
+    }
+    .into()
+}
+
+/// Mark the function that solves a part. Its first argument is the parsed input, either by
+/// reference or by value, and the rest are puzzle parameters, listed in the same order in
+/// `params(name = default, ...)`, and the `&mut Frames` to record a visualization in, if it
+/// draws one. It can return anything that implements `Display`, or a `Result` with an
+/// `ArithmeticError` for solutions with checked arithmetic, which can be generic over the
+/// `Int` type that `--int` chooses.
+///
+/// `stream = function` names a function that solves the part from a `LineReader` for
+/// `--stream`, taking the same parameters. It's generic over the integer type too, as its
+/// first type parameter, if the solution is.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attrs = SolutionAttrs::default();
+    let parser = syn::meta::parser(|meta| attrs.parse(meta));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    solution(attrs, function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct SolutionAttrs {
+    day: Option<LitInt>,
+    part: Option<LitInt>,
+    params: Vec<(Ident, Expr)>,
+    stream: Option<Path>,
+}
+
+impl SolutionAttrs {
+    fn parse(&mut self, meta: ParseNestedMeta<'_>) -> syn::Result<()> {
+        if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("part") {
+            self.part = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("params") {
+            meta.parse_nested_meta(|param| {
+                let name = param.path.require_ident()?.clone();
+                let default = param.value()?.parse()?;
+                self.params.push((name, default));
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("stream") {
+            self.stream = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `day`, `part`, `params` or `stream`"));
+        }
+        Ok(())
+    }
+}
+
+fn missing(attr: &str) -> Error {
+    Error::new(Span::call_site(), format!("missing `{attr} = ...`"))
+}
+
+/// Whether an argument is the `&mut Frames` that a solution records its visualization in.
+fn is_frames(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_some() => match &*reference.elem {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "Frames"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn solution(attrs: SolutionAttrs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let day = attrs.day.ok_or_else(|| missing("day"))?;
+    let part = attrs.part.ok_or_else(|| missing("part"))?;
+    let part_number: u32 = part.base10_parse()?;
+    if !(1..=2).contains(&part_number) {
+        return Err(Error::new(part.span(), "the part must be 1 or 2"));
+    }
+    let sig = &function.sig;
+    let generic = match sig.generics.params.len() {
+        0 => false,
+        1 if sig.generics.type_params().count() == 1 => true,
+        _ => {
+            return Err(Error::new(
+                sig.generics.span(),
+                "expected no generics, or just the integer type",
+            ))
+        }
+    };
+
+    let mut inputs = sig.inputs.iter();
+    let input = match inputs.next() {
+        Some(FnArg::Typed(input)) => input,
+        _ => {
+            return Err(Error::new(
+                sig.span(),
+                "expected the parsed input as the first argument",
+            ))
+        }
+    };
+    let input = match *input.ty {
+        Type::Reference(_) => quote!(&input),
+        _ => quote!(input),
+    };
+
+    // The arguments after the input, for running with the parameters from the command line,
+    // and with their defaults.
+    let (names, defaults): (Vec<_>, Vec<_>) = attrs.params.into_iter().unzip();
+    let keys: Vec<_> = names.iter().map(|name| name.to_string()).collect();
+    let mut params = names.iter().zip(&defaults);
+    let mut args = vec![];
+    let mut default_args = vec![];
+    let mut stream_args = vec![];
+    let mut draws = false;
+    for arg in inputs {
+        let FnArg::Typed(arg) = arg else {
+            return Err(Error::new(arg.span(), "expected a parameter"));
+        };
+        if is_frames(&arg.ty) {
+            draws = true;
+            args.push(quote!(&mut frames));
+            default_args.push(quote!(&mut frames));
+        } else if let Some((name, default)) = params.next() {
+            args.push(quote!(#name));
+            default_args.push(quote!(#default));
+            stream_args.push(quote!(#name));
+        } else {
+            return Err(Error::new(
+                arg.span(),
+                "expected a `params` entry for each argument after the input",
+            ));
+        }
+    }
+    if params.next().is_some() {
+        return Err(Error::new(
+            sig.inputs.span(),
+            "expected an argument after the input for each of the `params`",
+        ));
+    }
+
+    // A `Result` is assumed to be checked arithmetic, since that's the only error a solution
+    // can report once the input has parsed.
+    let checked = match &sig.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    };
+    let (solve, answer) = if checked {
+        (quote!(solve_checked), quote!(.map_err(|e| e.to_string())?))
+    } else {
+        (quote!(solve), quote!())
+    };
+
+    // Generic solutions run with the integer type from `--int`.
+    let name = &sig.ident;
+    let (solver, default_solver, stream_solver) = if generic {
+        let stream = attrs.stream.map(|stream| quote!(#stream::<N, _>));
+        (quote!(#name::<N>), quote!(#name::<i64>), stream)
+    } else {
+        let stream = attrs.stream.map(|stream| quote!(#stream));
+        (quote!(#name), quote!(#name), stream)
+    };
+    let with_int_type = |body: TokenStream2| {
+        if generic {
+            quote!(::common::__with_int_type!(args.int_type, N => #body))
+        } else {
+            body
+        }
+    };
+
+    let stream = match stream_solver {
+        Some(stream_solver) => {
+            let solve_stream = with_int_type(quote! {
+                report.solve_stream(|| #stream_solver(&mut lines, #(#stream_args),*))
+            });
+            quote! {
+                let mut lines = report.open_stream(file);
+                #(let #names = report.param(&args.params, #keys, #defaults);)*
+                #solve_stream;
+                return;
+            }
+        }
+        None => quote!(report.unsupported("--stream");),
+    };
+    let solve_input = with_int_type(quote!(report.#solve(|| #solver(#input, #(#args),*))));
+    let (start_drawing, show_drawing, no_drawing) = if draws {
+        (
+            quote!(let mut frames = args.visualize.frames();),
+            quote! {
+                args.visualize
+                    .show(&frames)
+                    .expect("failed to show the visualization");
+            },
+            quote!(let mut frames = ::common::visualize::Frames::disabled();),
+        )
+    } else {
+        (quote!(), quote!(), quote!())
+    };
+
+    let constant = format_ident!("__AOC_PART{}", part_number);
+    let message = format!("`#[aoc(day = {day})]` doesn't match the day of the parser");
+    Ok(quote! {
+        #function
+
+        const _: () = assert!(crate::__AOC_DAY == #day, #message);
+
+        #[doc(hidden)]
+        pub const #constant: ::common::solution::Solution = ::common::solution::Solution {
+            day: #day,
+            part: #part,
+            params: &[#(#keys),*],
+            run: |report, args, file| {
+                if args.streaming {
+                    #stream
+                }
+                let content = report.read_input(file);
+                let input = report.parse(|| {
+                    crate::__aoc_parse(::common::parse::Source::new(file, &content))
+                });
+                #(let #names = report.param(&args.params, #keys, #defaults);)*
+                #start_drawing
+                #solve_input;
+                #show_drawing
+            },
+            answer: |content| {
+                let content = ::common::input::normalize(content);
+                let input = crate::__aoc_parse(::common::parse::Source::new("input", &content))
+                    .map_err(|e| e.to_string())?;
+                #no_drawing
+                Ok(::common::answer::Answer::from_display(
+                    #default_solver(#input, #(#default_args),*)#answer,
+                ))
+            },
+        };
+    })
+}